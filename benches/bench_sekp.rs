@@ -1,13 +1,85 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_bigint::BigUint;
+use programming_bitcoin::finite_field::FiniteField;
+use programming_bitcoin::scalar_mul::multi_scalar_mul;
 use programming_bitcoin::secp256k1::*;
 
 fn big_mult() {
-    let g = SECP256K1_G.clone();
+    let g = *SECP256K1_G;
     let _ = g * SECP256K1_N.clone();
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("secp256k1_mul_by_order", |b| b.iter(|| big_mult()));
+    c.bench_function("secp256k1_mul_by_order", |b| b.iter(big_mult));
+
+    let k = SECP256K1_N.clone() - 12345u32;
+    let g = Secp256K1Point::generator();
+    c.bench_function("secp256k1_point_mul", |b| {
+        b.iter(|| black_box(g) * black_box(&k).clone())
+    });
+    c.bench_function("secp256k1_mul_generator", |b| {
+        b.iter(|| mul_generator(black_box(&k).clone()))
+    });
+    c.bench_function("secp256k1_mul_secret", |b| {
+        b.iter(|| black_box(g).mul_secret(black_box(&k)))
+    });
+    c.bench_function("secp256k1_public_key", |b| {
+        b.iter(|| public_key(black_box(&k)))
+    });
+
+    for n in [2u32, 16, 128, 192, 256, 384, 512] {
+        let terms: Vec<(BigUint, Secp256K1Point)> = (1..=n)
+            .map(|i| (k.clone() - i, mul_generator(i * 7919)))
+            .collect();
+        c.bench_function(&format!("secp256k1_naive_sum_{n}"), |b| {
+            b.iter(|| {
+                black_box(&terms)
+                    .iter()
+                    .fold(Secp256K1Point::infinity(), |acc, (k, p)| {
+                        acc + *p * k.clone()
+                    })
+            })
+        });
+        c.bench_function(&format!("secp256k1_multi_scalar_mul_{n}"), |b| {
+            b.iter(|| multi_scalar_mul(black_box(&terms)))
+        });
+    }
+
+    let x = SECP256K1_GX;
+    let y = SECP256K1_GY;
+    c.bench_function("secp256k1_field_add", |b| {
+        b.iter(|| black_box(&x) + black_box(&y))
+    });
+    c.bench_function("secp256k1_field_sub", |b| {
+        b.iter(|| black_box(&x) - black_box(&y))
+    });
+    c.bench_function("secp256k1_field_mul", |b| {
+        b.iter(|| black_box(&x) * black_box(&y))
+    });
+    c.bench_function("secp256k1_field_div", |b| {
+        b.iter(|| black_box(&x) / black_box(&y))
+    });
+
+    let x = FiniteField::<Secp256K1Mod>::from(SECP256K1_GX);
+    let y = FiniteField::<Secp256K1Mod>::from(SECP256K1_GY);
+    c.bench_function("secp256k1_biguint_field_add", |b| {
+        b.iter(|| black_box(&x) + black_box(&y))
+    });
+    c.bench_function("secp256k1_biguint_field_sub", |b| {
+        b.iter(|| black_box(&x) - black_box(&y))
+    });
+    c.bench_function("secp256k1_biguint_field_mul", |b| {
+        b.iter(|| black_box(&x) * black_box(&y))
+    });
+    c.bench_function("secp256k1_biguint_field_div", |b| {
+        b.iter(|| black_box(&x) / black_box(&y))
+    });
+    c.bench_function("secp256k1_biguint_field_inverse_vartime", |b| {
+        b.iter(|| black_box(&y).inverse_vartime())
+    });
+    c.bench_function("secp256k1_biguint_field_inverse_ct", |b| {
+        b.iter(|| black_box(&y).inverse_ct())
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
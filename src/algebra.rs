@@ -0,0 +1,23 @@
+//! The algebraic structures the curve arithmetic is generic over.
+//!
+//! [`Ring`] is what addition, subtraction and multiplication need, e.g. integers modulo a
+//! composite in [`ModRing`](crate::mod_ring::ModRing). [`Field`] adds division, which the
+//! chord-and-tangent formulas of [`EllipticCurve`](crate::ec::EllipticCurve) require.
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A commutative ring with identity. `From<u32>` maps an integer n to 1 + 1 + ... + 1.
+pub trait Ring:
+    Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Clone + PartialEq + From<u32>
+{
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn is_zero(&self) -> bool;
+}
+
+/// A ring in which every non-zero element has a multiplicative inverse.
+pub trait Field: Ring + Div<Output = Self> {
+    /// Returns the multiplicative inverse of this element, or `None` if it is zero.
+    fn inverse(&self) -> Option<Self>;
+}
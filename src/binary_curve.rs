@@ -0,0 +1,242 @@
+//! Elliptic curves over binary fields.
+//!
+//! In characteristic 2 the short Weierstrass form used by [`EllipticCurve`] is singular, so
+//! non-supersingular curves are written y^2 + xy = x^3 + ax^2 + b with b != 0 instead. The
+//! negative of (x, y) is (x, x + y), which changes every formula of the group law.
+//!
+//! [`EllipticCurve`]: crate::ec::EllipticCurve
+use std::ops::{Add, Mul};
+
+use num_bigint::BigUint;
+
+use crate::algebra::Field;
+use crate::ec::{Coordinates, PointType};
+
+// A binary elliptic curve defined by the equation y**2 + xy = x**3 + Ax**2 + B
+#[derive(Debug, Eq, PartialEq)]
+pub struct BinaryEllipticCurve<T> {
+    a: T,
+    b: T,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct BinaryCurvePoint<'a, T> {
+    curve: &'a BinaryEllipticCurve<T>,
+    pub p: PointType<T>,
+}
+
+impl<'a, T> BinaryEllipticCurve<T>
+where
+    T: Field,
+{
+    /// Creates the curve y^2 + xy = x^3 + ax^2 + b.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is zero, which makes the curve singular.
+    pub fn new(a: T, b: T) -> Self {
+        assert!(!b.is_zero(), "b must be non-zero");
+        Self { a, b }
+    }
+
+    pub fn point_at(&'a self, x: T, y: T) -> BinaryCurvePoint<'a, T> {
+        match self.contains(&x, &y) {
+            false => BinaryCurvePoint {
+                curve: self,
+                p: PointType::Invalid,
+            },
+            true => BinaryCurvePoint {
+                curve: self,
+                p: PointType::Point(Coordinates { x, y }),
+            },
+        }
+    }
+
+    pub fn infinity(&'a self) -> BinaryCurvePoint<'a, T> {
+        BinaryCurvePoint {
+            curve: self,
+            p: PointType::Infinity,
+        }
+    }
+
+    pub fn contains(&self, x: &T, y: &T) -> bool {
+        let lhs = y.clone() * y.clone() + x.clone() * y.clone();
+        let x2 = x.clone() * x.clone();
+        let rhs = x2.clone() * x.clone() + self.a.clone() * x2 + self.b.clone();
+        lhs == rhs
+    }
+}
+
+impl<'a, T> Add for &BinaryCurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = BinaryCurvePoint<'a, T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert!(
+            self.curve == rhs.curve,
+            "Cannot add points from different curves"
+        );
+
+        let (p, q) = match (&self.p, &rhs.p) {
+            (PointType::Infinity, _) => return rhs.clone(),
+            (_, PointType::Infinity) => return self.clone(),
+            (PointType::Invalid, _) | (_, PointType::Invalid) => {
+                return BinaryCurvePoint {
+                    curve: self.curve,
+                    p: PointType::Invalid,
+                }
+            }
+            (PointType::Point(p1), PointType::Point(p2)) => (p1, p2),
+        };
+        let infinity = BinaryCurvePoint {
+            curve: self.curve,
+            p: PointType::Infinity,
+        };
+
+        let (x, y) = match p == q {
+            // Doubling: the tangent is vertical at x = 0, where P = -P.
+            true => {
+                if p.x.is_zero() {
+                    return infinity;
+                }
+                let s = p.x.clone() + p.y.clone() / p.x.clone();
+                let x = s.clone() * s.clone() + s.clone() + self.curve.a.clone();
+                let y = p.x.clone() * p.x.clone() + (s + T::one()) * x.clone();
+                (x, y)
+            }
+            // Q = -P = (x, x + y) also shares the x coordinate, and P + Q is at infinity.
+            false if p.x == q.x => return infinity,
+            false => {
+                let s = (p.y.clone() + q.y.clone()) / (p.x.clone() + q.x.clone());
+                let x = s.clone() * s.clone()
+                    + s.clone()
+                    + p.x.clone()
+                    + q.x.clone()
+                    + self.curve.a.clone();
+                let y = s * (p.x.clone() + x.clone()) + x.clone() + p.y.clone();
+                (x, y)
+            }
+        };
+
+        BinaryCurvePoint {
+            curve: self.curve,
+            p: PointType::Point(Coordinates { x, y }),
+        }
+    }
+}
+
+// T + T
+impl<'a, T> Add for BinaryCurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = BinaryCurvePoint<'a, T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+// Base implementation: &T * U
+impl<'a, T, U> Mul<U> for &BinaryCurvePoint<'a, T>
+where
+    T: Field,
+    U: Into<BigUint>,
+{
+    type Output = BinaryCurvePoint<'a, T>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        let n: BigUint = rhs.into();
+        let mut result = self.curve.infinity();
+        for i in (0..n.bits()).rev() {
+            result = &result + &result;
+            if n.bit(i) {
+                result = &result + self;
+            }
+        }
+        result
+    }
+}
+
+// T * U
+impl<'a, T, U> Mul<U> for BinaryCurvePoint<'a, T>
+where
+    T: Field,
+    U: Into<BigUint>,
+{
+    type Output = BinaryCurvePoint<'a, T>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        &self * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_field::BinaryField;
+    use crate::define_binary_field;
+
+    define_binary_field!(F16 = [4, 1, 0]);
+
+    type Fe = BinaryField<F16, 1>;
+
+    fn all_points(c: &BinaryEllipticCurve<Fe>) -> Vec<BinaryCurvePoint<'_, Fe>> {
+        let mut points = vec![c.infinity()];
+        for x in 0u32..16 {
+            for y in 0u32..16 {
+                let p = c.point_at(Fe::new(x), Fe::new(y));
+                if p.p != PointType::Invalid {
+                    points.push(p);
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_koblitz_point_count() {
+        // y^2 + xy = x^3 + 1 has 4 points over GF(2), so its trace is t = -1, and over
+        // GF(2^k) it has 2^k + 1 - s_k points with s_k = t s_(k-1) - 2 s_(k-2): 16 for k = 4.
+        let c = BinaryEllipticCurve::new(Fe::zero(), Fe::one());
+        assert_eq!(all_points(&c).len(), 16);
+    }
+
+    #[test]
+    fn test_group_law() {
+        // a = x^3, b = x^3 + 1
+        let c = BinaryEllipticCurve::new(Fe::new(0b1000u32), Fe::new(0b1001u32));
+        let points = all_points(&c);
+        let n = points.len() as u32;
+        for p in &points {
+            assert_eq!(p * n, c.infinity());
+            for q in &points {
+                let sum = p + q;
+                assert_ne!(sum.p, PointType::Invalid);
+                assert_eq!(sum, q + p);
+                for r in &points {
+                    assert_eq!(&sum + r, p + &(q + r));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_negation() {
+        let c = BinaryEllipticCurve::new(Fe::zero(), Fe::one());
+        for p in all_points(&c) {
+            if let PointType::Point(Coordinates { x, y }) = p.p {
+                let minus_p = c.point_at(x, x + y);
+                assert_eq!(p + minus_p, c.infinity());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "b must be non-zero")]
+    fn test_singular() {
+        let _ = BinaryEllipticCurve::new(Fe::one(), Fe::zero());
+    }
+}
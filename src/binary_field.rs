@@ -0,0 +1,550 @@
+//! Binary extension fields GF(2^m) in polynomial basis.
+//!
+//! An element is a polynomial of degree below m with coefficients in GF(2), stored as a bit
+//! vector in little-endian 64-bit limbs. Addition is XOR, and multiplication is a carry-less
+//! product followed by reduction modulo an irreducible polynomial f of degree m.
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Sub};
+
+use num_bigint::BigUint;
+
+use crate::algebra::{Field, Ring};
+use crate::error::{Error, Result};
+
+/// The reduction polynomial of GF(2^m), for m at most 64 * `N`.
+#[derive(Debug)]
+pub struct BinaryConstants<const N: usize> {
+    /// The degree m of the extension.
+    pub degree: usize,
+    /// The exponents of the non-leading terms of f, highest first, e.g. `[7, 6, 3, 0]` for
+    /// f = x^163 + x^7 + x^6 + x^3 + 1.
+    pub low_terms: Vec<usize>,
+}
+
+impl<const N: usize> BinaryConstants<N> {
+    /// Builds the constants for the polynomial with the given exponents, highest first, e.g.
+    /// `&[163, 7, 6, 3, 0]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the exponents are not strictly decreasing, the degree does not fit in `N`
+    /// limbs or is below 2, or the polynomial is not irreducible over GF(2).
+    pub fn new(exponents: &[usize]) -> Self {
+        assert!(
+            exponents.windows(2).all(|w| w[0] > w[1]),
+            "exponents {:?} are not strictly decreasing",
+            exponents
+        );
+        let degree = exponents[0];
+        assert!(
+            degree >= 2,
+            "reduction polynomial must have degree at least 2"
+        );
+        assert!(
+            degree <= 64 * N,
+            "degree {} does not fit in {} limbs",
+            degree,
+            N
+        );
+
+        let mut f = BigUint::from(0u32);
+        for e in exponents {
+            f.set_bit(*e as u64, true);
+        }
+        assert!(
+            gf2_is_irreducible(&f),
+            "reduction polynomial {:?} is not irreducible",
+            exponents
+        );
+
+        Self {
+            degree,
+            low_terms: exponents[1..].to_vec(),
+        }
+    }
+}
+
+/// The product of two GF(2) polynomials given as bit vectors.
+fn gf2_mul(a: &BigUint, b: &BigUint) -> BigUint {
+    let mut r = BigUint::from(0u32);
+    for i in 0..b.bits() {
+        if b.bit(i) {
+            r ^= a << i;
+        }
+    }
+    r
+}
+
+/// The remainder of `a` divided by the non-zero GF(2) polynomial `m`.
+fn gf2_rem(a: &BigUint, m: &BigUint) -> BigUint {
+    let mut a = a.clone();
+    while a.bits() >= m.bits() {
+        a ^= m << (a.bits() - m.bits());
+    }
+    a
+}
+
+/// Ben-Or's test over GF(2): f of degree m is irreducible exactly when it has no common
+/// factor with x^(2^i) - x for any 1 <= i <= m/2.
+fn gf2_is_irreducible(f: &BigUint) -> bool {
+    let x = BigUint::from(2u32);
+    let mut x_2_i = x.clone();
+    for _ in 0..(f.bits() - 1) / 2 {
+        x_2_i = gf2_rem(&gf2_mul(&x_2_i, &x_2_i), f);
+        let (mut a, mut b) = (f.clone(), &x_2_i ^ &x);
+        while b != 0u32.into() {
+            let r = gf2_rem(&a, &b);
+            a = b;
+            b = r;
+        }
+        if a != 1u32.into() {
+            return false;
+        }
+    }
+    true
+}
+
+/// The reduction polynomial of a binary field.
+///
+/// Like [`FieldMod`](crate::finite_field::FieldMod), implementors build their
+/// [`BinaryConstants`] once and hand out a `'static` reference to it.
+/// [`define_binary_field!`] generates such an implementation.
+pub trait BinaryMod<const N: usize>: Clone + PartialEq + 'static {
+    fn constants() -> &'static BinaryConstants<N>;
+}
+
+/// Defines a marker type implementing [`BinaryMod`] for the reduction polynomial with the
+/// given exponents, highest first. The number of limbs is derived from the degree.
+///
+/// ```
+/// use programming_bitcoin::binary_field::BinaryField;
+/// use programming_bitcoin::define_binary_field;
+///
+/// define_binary_field! {
+///     /// GF(2^4) with f = x^4 + x + 1.
+///     pub F16 = [4, 1, 0]
+/// }
+///
+/// let x = BinaryField::<F16, 1>::new(0b10u32);
+/// assert_eq!(x * BinaryField::new(0b1000u32), BinaryField::new(0b0011u32));
+/// ```
+#[macro_export]
+macro_rules! define_binary_field {
+    ($(#[$meta:meta])* $vis:vis $name:ident = [$degree:literal $(, $exp:literal)* $(,)?]) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis struct $name;
+
+        impl $crate::binary_field::BinaryMod<{ ($degree as usize).div_ceil(64) }> for $name {
+            fn constants(
+            ) -> &'static $crate::binary_field::BinaryConstants<{ ($degree as usize).div_ceil(64) }>
+            {
+                static CONSTANTS: ::std::sync::OnceLock<
+                    $crate::binary_field::BinaryConstants<{ ($degree as usize).div_ceil(64) }>,
+                > = ::std::sync::OnceLock::new();
+                CONSTANTS.get_or_init(|| {
+                    $crate::binary_field::BinaryConstants::new(&[$degree $(, $exp)*])
+                })
+            }
+        }
+    };
+}
+
+/// An element of GF(2^m) as `N` little-endian limbs, always reduced below degree m.
+#[derive(PartialEq, Eq, Hash)]
+pub struct BinaryField<M: BinaryMod<N>, const N: usize> {
+    limbs: [u64; N],
+    _phantom: std::marker::PhantomData<M>,
+}
+
+// Implemented by hand so that the marker type does not need to be `Copy`.
+impl<M: BinaryMod<N>, const N: usize> Clone for BinaryField<M, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: BinaryMod<N>, const N: usize> Copy for BinaryField<M, N> {}
+
+/// The carry-less product of two words as (low, high).
+fn clmul(a: u64, b: u64) -> (u64, u64) {
+    let (mut lo, mut hi) = (0u64, 0u64);
+    for i in 0..64 {
+        let mask = 0u64.wrapping_sub((b >> i) & 1);
+        lo ^= (a << i) & mask;
+        // a >> 64 would overflow, and bit 0 of b never reaches the high word.
+        if i > 0 {
+            hi ^= (a >> (64 - i)) & mask;
+        }
+    }
+    (lo, hi)
+}
+
+impl<M: BinaryMod<N>, const N: usize> BinaryField<M, N> {
+    fn from_limbs(limbs: [u64; N]) -> Self {
+        Self {
+            limbs,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a new element from its bit vector: bit i is the coefficient of x^i.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` has degree m or more. Use [`BinaryField::try_new`] for values coming
+    /// from untrusted input.
+    pub fn new(bits: impl Into<BigUint>) -> Self {
+        match Self::try_new(bits) {
+            Ok(fe) => fe,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Creates a new element from its bit vector, failing if it has degree m or more.
+    pub fn try_new(bits: impl Into<BigUint>) -> Result<Self> {
+        let bits = bits.into();
+        let degree = M::constants().degree;
+        if bits.bits() > degree as u64 {
+            return Err(Error::OutOfRange {
+                value: bits,
+                modulus: BigUint::from(1u32) << degree,
+            });
+        }
+        let mut limbs = [0u64; N];
+        for (limb, digit) in limbs.iter_mut().zip(bits.iter_u64_digits()) {
+            *limb = digit;
+        }
+        Ok(Self::from_limbs(limbs))
+    }
+
+    pub fn zero() -> Self {
+        Self::from_limbs([0; N])
+    }
+
+    pub fn one() -> Self {
+        let mut limbs = [0; N];
+        limbs[0] = 1;
+        Self::from_limbs(limbs)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|l| *l == 0)
+    }
+
+    /// The bit vector of this element.
+    pub fn to_biguint(&self) -> BigUint {
+        let digits: Vec<u32> = self
+            .limbs
+            .iter()
+            .flat_map(|l| [*l as u32, (*l >> 32) as u32])
+            .collect();
+        BigUint::from_slice(&digits)
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    pub fn exp(&self, exponent: impl Into<BigUint>) -> Self {
+        let exponent = exponent.into();
+        let mut result = Self::one();
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = result * *self;
+            }
+        }
+        result
+    }
+
+    /// Returns the multiplicative inverse of this element, or `None` if it is zero.
+    ///
+    /// Computes a^(2^m - 2) = a^2 * a^4 * ... * a^(2^(m-1)) with m - 1 squarings.
+    pub fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut power = *self;
+        let mut result = Self::one();
+        for _ in 1..M::constants().degree {
+            power = power.square();
+            result = result * power;
+        }
+        Some(result)
+    }
+
+    /// Computes `self / other`, returning `None` if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inv| *self * inv)
+    }
+
+    /// The unique square root a^(2^(m-1)). Squaring is a bijection in characteristic 2.
+    pub fn sqrt(&self) -> Self {
+        let mut root = *self;
+        for _ in 1..M::constants().degree {
+            root = root.square();
+        }
+        root
+    }
+
+    /// The absolute trace a + a^2 + a^4 + ... + a^(2^(m-1)), which is always 0 or 1. The
+    /// equation z^2 + z = a has a solution exactly when the trace of a is 0.
+    pub fn trace(&self) -> Self {
+        let mut power = *self;
+        let mut trace = *self;
+        for _ in 1..M::constants().degree {
+            power = power.square();
+            trace = trace + power;
+        }
+        trace
+    }
+}
+
+// T + T
+impl<M: BinaryMod<N>, const N: usize> Add for BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    // Coefficients are added in GF(2), which is XOR.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self::Output {
+        let mut limbs = self.limbs;
+        for (a, b) in limbs.iter_mut().zip(&other.limbs) {
+            *a ^= b;
+        }
+        BinaryField::from_limbs(limbs)
+    }
+}
+
+// &T + &T
+impl<M: BinaryMod<N>, const N: usize> Add for &BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    fn add(self, other: Self) -> Self::Output {
+        *self + *other
+    }
+}
+
+// T - T
+impl<M: BinaryMod<N>, const N: usize> Sub for BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    // Every element is its own negative, so subtraction is addition.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self::Output {
+        self + other
+    }
+}
+
+// &T - &T
+impl<M: BinaryMod<N>, const N: usize> Sub for &BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        *self - *other
+    }
+}
+
+// T * T
+impl<M: BinaryMod<N>, const N: usize> Mul for BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let mut product = vec![0u64; 2 * N];
+        for (i, a) in self.limbs.iter().enumerate() {
+            for (j, b) in other.limbs.iter().enumerate() {
+                let (lo, hi) = clmul(*a, *b);
+                product[i + j] ^= lo;
+                product[i + j + 1] ^= hi;
+            }
+        }
+
+        // Clear every bit at or above x^m from the top down, using x^m = sum of the low terms.
+        let constants = M::constants();
+        let m = constants.degree;
+        for pos in (m..2 * m - 1).rev() {
+            if (product[pos / 64] >> (pos % 64)) & 1 == 0 {
+                continue;
+            }
+            product[pos / 64] ^= 1 << (pos % 64);
+            for e in &constants.low_terms {
+                let target = pos - m + e;
+                product[target / 64] ^= 1 << (target % 64);
+            }
+        }
+
+        let mut limbs = [0u64; N];
+        limbs.copy_from_slice(&product[..N]);
+        BinaryField::from_limbs(limbs)
+    }
+}
+
+// &T * &T
+impl<M: BinaryMod<N>, const N: usize> Mul for &BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        *self * *other
+    }
+}
+
+// T / T
+impl<M: BinaryMod<N>, const N: usize> Div for BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.checked_div(&other).expect("attempt to divide by zero")
+    }
+}
+
+// &T / &T
+impl<M: BinaryMod<N>, const N: usize> Div for &BinaryField<M, N> {
+    type Output = BinaryField<M, N>;
+
+    fn div(self, other: Self) -> Self::Output {
+        *self / *other
+    }
+}
+
+impl<M: BinaryMod<N>, const N: usize> Ring for BinaryField<M, N> {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl<M: BinaryMod<N>, const N: usize> Field for BinaryField<M, N> {
+    fn inverse(&self) -> Option<Self> {
+        self.inverse()
+    }
+}
+
+/// Maps n to 1 + 1 + ... + 1, which is n mod 2 in characteristic 2. Use
+/// [`BinaryField::new`] to build an element from its bit vector.
+impl<M: BinaryMod<N>, const N: usize> From<u32> for BinaryField<M, N> {
+    fn from(value: u32) -> Self {
+        match value & 1 {
+            0 => Self::zero(),
+            _ => Self::one(),
+        }
+    }
+}
+
+impl<M: BinaryMod<N>, const N: usize> Debug for BinaryField<M, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BinaryField(0x{:x})", self.to_biguint())
+    }
+}
+
+impl<M: BinaryMod<N>, const N: usize> Display for BinaryField<M, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FieldElement<2^{}>(0x{:x})",
+            M::constants().degree,
+            self.to_biguint()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    define_binary_field!(F16 = [4, 1, 0]);
+    define_binary_field!(F256 = [8, 4, 3, 1, 0]);
+    define_binary_field!(Sect163 = [163, 7, 6, 3, 0]);
+
+    type Fe16 = BinaryField<F16, 1>;
+    type Fe256 = BinaryField<F256, 1>;
+    type Fe163 = BinaryField<Sect163, 3>;
+
+    #[test]
+    fn test_clmul() {
+        assert_eq!(clmul(0b11, 0b11), (0b101, 0));
+        assert_eq!(clmul(u64::MAX, 2), (u64::MAX << 1, 1));
+        assert_eq!(clmul(1 << 63, 1 << 63), (0, 1 << 62));
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        // Schoolbook GF(2) product reduced bit by bit, exhaustively over GF(2^8).
+        let f = BigUint::from(0x11Bu32);
+        for a in 0u32..256 {
+            for b in 0u32..256 {
+                let expected = gf2_rem(&gf2_mul(&a.into(), &b.into()), &f);
+                assert_eq!((Fe256::new(a) * Fe256::new(b)).to_biguint(), expected);
+            }
+        }
+        // The AES example from FIPS-197: {57} * {83} = {c1}.
+        assert_eq!(
+            Fe256::new(0x57u32) * Fe256::new(0x83u32),
+            Fe256::new(0xC1u32)
+        );
+    }
+
+    #[test]
+    fn test_field_axioms() {
+        for a in 0u32..16 {
+            let a = Fe16::new(a);
+            assert!((a + a).is_zero());
+            assert_eq!(a - a, Fe16::zero());
+            assert_eq!(a.sqrt().square(), a);
+            assert!(a.trace() == Fe16::zero() || a.trace() == Fe16::one());
+            match a.inverse() {
+                Some(inv) => assert_eq!(a * inv, Fe16::one()),
+                None => assert!(a.is_zero()),
+            }
+        }
+        // Half of the elements have trace zero.
+        let zeros = (0u32..16)
+            .filter(|a| Fe16::new(*a).trace().is_zero())
+            .count();
+        assert_eq!(zeros, 8);
+    }
+
+    #[test]
+    fn test_large_field() {
+        let a = Fe163::new(
+            BigUint::parse_bytes(b"2FE13C0537BBC11ACAA07D793DE4E6D5E5C94EEE8", 16).unwrap(),
+        );
+        let b = Fe163::new(
+            BigUint::parse_bytes(b"289070FB05D38FF58321F2E800536D538CCDAA3D9", 16).unwrap(),
+        );
+        assert_eq!(a * a.inverse().unwrap(), Fe163::one());
+        assert_eq!((a * b) / b, a);
+        assert_eq!(a.sqrt().square(), a);
+        // x^163 = x^7 + x^6 + x^3 + 1
+        let x = Fe163::new(2u32);
+        assert_eq!(x.exp(163u32), Fe163::new(0b11001001u32));
+        // The multiplicative group has order 2^163 - 1.
+        assert_eq!(a.exp((BigUint::from(1u32) << 163) - 1u32), Fe163::one());
+    }
+
+    #[test]
+    fn test_from_u32() {
+        assert_eq!(Fe16::from(2u32), Fe16::zero());
+        assert_eq!(Fe16::from(3u32), Fe16::one());
+        assert!(Fe16::try_new(16u32).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "not irreducible")]
+    fn test_reducible() {
+        // x^4 + 1 = (x + 1)^4
+        define_binary_field!(Reducible = [4, 0]);
+        let _ = Reducible::constants();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Fe16::new(0b1011u32).to_string(), "FieldElement<2^4>(0xb)");
+    }
+}
@@ -0,0 +1,548 @@
+//! Elliptic curves whose parameters are types.
+//!
+//! [`ECurvePoint`] borrows the [`EllipticCurve`] it lies on, so points carry a lifetime and every
+//! addition compares the two curves at runtime. Here the curve is a zero-sized type implementing
+//! [`CurveParams`] instead, so a [`Point`] owns everything it needs, is `'static`, and adding
+//! points of different curves does not compile.
+//!
+//! [`ECurvePoint`]: crate::ec::ECurvePoint
+//! [`EllipticCurve`]: crate::ec::EllipticCurve
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num_bigint::BigUint;
+
+use crate::algebra::{Field, Ring};
+use crate::ec::{decode_sec, encode_sec, Coordinates, PointType, SecField};
+use crate::error::Result;
+use crate::jacobian::Jacobian;
+
+/// The parameters of a curve y^2 = x^3 + ax + b with a generator of prime order.
+///
+/// Implementors are zero-sized marker types; the curve is identified by the type alone.
+pub trait CurveParams: Clone + PartialEq + 'static {
+    type Field: Field + Debug;
+
+    fn a() -> Self::Field;
+
+    fn b() -> Self::Field;
+
+    /// The affine coordinates of the generator.
+    fn generator() -> Coordinates<Self::Field>;
+
+    /// The order of the generator.
+    fn order() -> BigUint;
+
+    /// The number of points on the curve divided by the order of the generator.
+    fn cofactor() -> u32;
+}
+
+/// A point on the curve `C`.
+pub struct Point<C: CurveParams> {
+    pub p: PointType<C::Field>,
+    _curve: PhantomData<fn() -> C>,
+}
+
+impl<C: CurveParams> Point<C> {
+    pub(crate) fn from_type(p: PointType<C::Field>) -> Self {
+        Self {
+            p,
+            _curve: PhantomData,
+        }
+    }
+
+    /// Returns the point (x, y), or an invalid point if it is not on the curve.
+    pub fn new(x: impl Into<C::Field>, y: impl Into<C::Field>) -> Self {
+        let x = x.into();
+        let y = y.into();
+        match Self::contains(&x, &y) {
+            false => Self::from_type(PointType::Invalid),
+            true => Self::from_type(PointType::Point(Coordinates { x, y })),
+        }
+    }
+
+    pub fn infinity() -> Self {
+        Self::from_type(PointType::Infinity)
+    }
+
+    pub fn generator() -> Self {
+        Self::from_type(PointType::Point(C::generator()))
+    }
+
+    pub fn contains(x: &C::Field, y: &C::Field) -> bool {
+        let lhs = y.clone() * y.clone();
+        let x3 = x.clone() * x.clone() * x.clone();
+        let rhs = x3 + C::a() * x.clone() + C::b();
+        lhs == rhs
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.p == PointType::Infinity
+    }
+
+    /// Whether this is the point at infinity or a point on the curve.
+    pub fn is_valid(&self) -> bool {
+        self.p != PointType::Invalid
+    }
+
+    /// The affine coordinates, or `None` for the point at infinity and invalid points.
+    pub fn coordinates(&self) -> Option<&Coordinates<C::Field>> {
+        match &self.p {
+            PointType::Point(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        self + self
+    }
+}
+
+impl<C: CurveParams> Point<C>
+where
+    C::Field: SecField,
+{
+    /// The SEC 1 encoding of this point, as in [`ECurvePoint::to_sec`]. Returns `None` for an
+    /// invalid point.
+    ///
+    /// [`ECurvePoint::to_sec`]: crate::ec::ECurvePoint::to_sec
+    pub fn to_sec(&self, compressed: bool) -> Option<Vec<u8>> {
+        encode_sec(&self.p, compressed)
+    }
+
+    /// Decodes a SEC 1 point encoding, as in [`EllipticCurve::parse_sec`].
+    ///
+    /// [`EllipticCurve::parse_sec`]: crate::ec::EllipticCurve::parse_sec
+    pub fn from_sec(bytes: &[u8]) -> Result<Self> {
+        decode_sec(bytes, &C::a(), &C::b()).map(Self::from_type)
+    }
+}
+
+// Implemented by hand so that `C` itself needs none of these traits.
+impl<C: CurveParams> Clone for Point<C> {
+    fn clone(&self) -> Self {
+        Self::from_type(self.p.clone())
+    }
+}
+
+impl<C: CurveParams> Copy for Point<C> where C::Field: Copy {}
+
+impl<C: CurveParams> PartialEq for Point<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.p == other.p
+    }
+}
+
+impl<C: CurveParams> Eq for Point<C> where C::Field: Eq {}
+
+impl<C: CurveParams> Debug for Point<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Point").field(&self.p).finish()
+    }
+}
+
+impl<C: CurveParams> Add for &Point<C> {
+    type Output = Point<C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (p, q) = match (&self.p, &rhs.p) {
+            (PointType::Infinity, _) => return rhs.clone(),
+            (_, PointType::Infinity) => return self.clone(),
+            (PointType::Invalid, _) | (_, PointType::Invalid) => {
+                return Point::from_type(PointType::Invalid)
+            }
+            (PointType::Point(p1), PointType::Point(p2)) => (p1, p2),
+        };
+
+        // Additive inverses, or a point of order two doubled: the line is vertical.
+        if p.x == q.x && (p.y != q.y || p.y.is_zero()) {
+            return Point::infinity();
+        }
+
+        let s = match p == q {
+            true => {
+                let three: C::Field = 3u32.into();
+                let two: C::Field = 2u32.into();
+                (three * p.x.clone() * p.x.clone() + C::a()) / (two * p.y.clone())
+            }
+            false => (q.y.clone() - p.y.clone()) / (q.x.clone() - p.x.clone()),
+        };
+
+        let x = s.clone() * s.clone() - p.x.clone() - q.x.clone();
+        let y = s * (p.x.clone() - x.clone()) - p.y.clone();
+        Point::from_type(PointType::Point(Coordinates { x, y }))
+    }
+}
+
+// T + T
+impl<C: CurveParams> Add for Point<C> {
+    type Output = Point<C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+// T + &T
+impl<C: CurveParams> Add<&Point<C>> for Point<C> {
+    type Output = Point<C>;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        &self + rhs
+    }
+}
+
+// &T + T
+impl<C: CurveParams> Add<Point<C>> for &Point<C> {
+    type Output = Point<C>;
+
+    fn add(self, rhs: Point<C>) -> Self::Output {
+        self + &rhs
+    }
+}
+
+// T += T
+impl<C: CurveParams> AddAssign for Point<C> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = &*self + &rhs;
+    }
+}
+
+// T += &T
+impl<C: CurveParams> AddAssign<&Point<C>> for Point<C> {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self = &*self + rhs;
+    }
+}
+
+// Base implementation: -&T
+impl<C: CurveParams> Neg for &Point<C> {
+    type Output = Point<C>;
+
+    fn neg(self) -> Self::Output {
+        match &self.p {
+            PointType::Infinity | PointType::Invalid => self.clone(),
+            PointType::Point(c) => Point::from_type(PointType::Point(Coordinates {
+                x: c.x.clone(),
+                y: C::Field::zero() - c.y.clone(),
+            })),
+        }
+    }
+}
+
+// -T
+impl<C: CurveParams> Neg for Point<C> {
+    type Output = Point<C>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+// Base implementation: &T - &T
+impl<C: CurveParams> Sub for &Point<C> {
+    type Output = Point<C>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+// T - T
+impl<C: CurveParams> Sub for Point<C> {
+    type Output = Point<C>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+// T - &T
+impl<C: CurveParams> Sub<&Point<C>> for Point<C> {
+    type Output = Point<C>;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        &self - rhs
+    }
+}
+
+// &T - T
+impl<C: CurveParams> Sub<Point<C>> for &Point<C> {
+    type Output = Point<C>;
+
+    fn sub(self, rhs: Point<C>) -> Self::Output {
+        self - &rhs
+    }
+}
+
+// T -= T
+impl<C: CurveParams> SubAssign for Point<C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = &*self - &rhs;
+    }
+}
+
+// T -= &T
+impl<C: CurveParams> SubAssign<&Point<C>> for Point<C> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self = &*self - rhs;
+    }
+}
+
+impl<C: CurveParams> Sum for Point<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Point::infinity(), |acc, p| acc + p)
+    }
+}
+
+impl<'a, C: CurveParams> Sum<&'a Point<C>> for Point<C> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Point::infinity(), |acc, p| acc + p)
+    }
+}
+
+// Base implementation: &T * U
+// Variable time. Secret scalars go through `Point::mul_secret` instead.
+impl<C: CurveParams, U: Into<BigUint>> Mul<U> for &Point<C> {
+    type Output = Point<C>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        match &self.p {
+            PointType::Infinity | PointType::Invalid => self.clone(),
+            PointType::Point(p) => {
+                let n: BigUint = rhs.into();
+                Point::from_type(Jacobian::mul(p, &n, &C::a()).to_affine())
+            }
+        }
+    }
+}
+
+// T * U
+impl<C: CurveParams, U: Into<BigUint>> Mul<U> for Point<C> {
+    type Output = Point<C>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        &self * rhs
+    }
+}
+
+// T *= U
+impl<C: CurveParams, U: Into<BigUint>> MulAssign<U> for Point<C> {
+    fn mul_assign(&mut self, rhs: U) {
+        *self = &*self * rhs;
+    }
+}
+
+/// Curves shared by the tests of the point arithmetic modules.
+#[cfg(test)]
+pub(crate) mod test_curves {
+    use num_bigint::BigUint;
+
+    use super::CurveParams;
+    use crate::define_prime_field;
+    use crate::ec::Coordinates;
+    use crate::finite_field::FiniteField;
+
+    define_prime_field!(pub(crate) Field97Mod = 97u32);
+
+    pub(crate) type Field97 = FiniteField<Field97Mod>;
+
+    /// y^2 = x^3 + 2x + 14 over F_97, with 101 points generated by (3, 12). The order is prime,
+    /// as the complete formulas require, and the non-zero a exercises the a terms of doubling.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct Toy97;
+
+    impl CurveParams for Toy97 {
+        type Field = Field97;
+
+        fn a() -> Field97 {
+            2u32.into()
+        }
+
+        fn b() -> Field97 {
+            14u32.into()
+        }
+
+        fn generator() -> Coordinates<Field97> {
+            Coordinates {
+                x: 3u32.into(),
+                y: 12u32.into(),
+            }
+        }
+
+        fn order() -> BigUint {
+            101u32.into()
+        }
+
+        fn cofactor() -> u32 {
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+    use crate::error::Error;
+    use crate::finite_field::FiniteField;
+
+    define_prime_field!(Field223Mod = 223u32);
+
+    type Field223 = FiniteField<Field223Mod>;
+
+    /// y^2 = x^3 + 7 over F_223, with the generator (47, 71) of order 21.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Toy;
+
+    impl CurveParams for Toy {
+        type Field = Field223;
+
+        fn a() -> Field223 {
+            0u32.into()
+        }
+
+        fn b() -> Field223 {
+            7u32.into()
+        }
+
+        fn generator() -> Coordinates<Field223> {
+            Coordinates {
+                x: 47u32.into(),
+                y: 71u32.into(),
+            }
+        }
+
+        fn order() -> BigUint {
+            21u32.into()
+        }
+
+        fn cofactor() -> u32 {
+            12
+        }
+    }
+
+    type ToyPoint = Point<Toy>;
+
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<ToyPoint>();
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(ToyPoint::new(47u32, 71u32), ToyPoint::generator());
+        assert!(!ToyPoint::new(200u32, 119u32).is_valid());
+    }
+
+    #[test]
+    fn test_add() {
+        let a = ToyPoint::new(192u32, 105u32);
+        let b = ToyPoint::new(17u32, 56u32);
+        assert_eq!(&a + &b, ToyPoint::new(170u32, 142u32));
+        assert_eq!(a.clone() + b.clone(), b.clone() + a.clone());
+        assert_eq!(a.clone() + ToyPoint::infinity(), a);
+
+        let minus_a = ToyPoint::new(192u32, 223u32 - 105);
+        assert_eq!(a + minus_a, ToyPoint::infinity());
+    }
+
+    #[test]
+    fn test_mul() {
+        let g = ToyPoint::generator();
+        assert_eq!(&g * 2u32, ToyPoint::new(36u32, 111u32));
+        assert_eq!(&g * 2u32, &g + &g);
+        assert_eq!(&g * Toy::order(), ToyPoint::infinity());
+        for k in 1u32..21 {
+            assert_ne!(&g * k, ToyPoint::infinity());
+        }
+    }
+
+    #[test]
+    fn test_accessors() {
+        let g = ToyPoint::generator();
+        assert_eq!(g.coordinates(), Some(&Toy::generator()));
+        assert!(!g.is_infinity() && g.is_valid());
+        assert!(ToyPoint::infinity().is_infinity());
+        assert_eq!(ToyPoint::infinity().coordinates(), None);
+        assert_eq!(ToyPoint::new(200u32, 119u32).coordinates(), None);
+        assert_eq!(g.double(), ToyPoint::new(36u32, 111u32));
+    }
+
+    #[test]
+    fn test_neg_sub() {
+        let a = ToyPoint::new(192u32, 105u32);
+        let b = ToyPoint::new(17u32, 56u32);
+        assert_eq!(-&a, ToyPoint::new(192u32, 223u32 - 105));
+        assert_eq!(&a + -&a, ToyPoint::infinity());
+        assert_eq!(-ToyPoint::infinity(), ToyPoint::infinity());
+        assert_eq!(&(&a + &b) - &b, a);
+        assert_eq!(a.clone() - a.clone(), ToyPoint::infinity());
+        assert_eq!(&a - ToyPoint::infinity(), a);
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let g = ToyPoint::generator();
+        let mut p = g.clone();
+        p += &g;
+        assert_eq!(p, &g * 2u32);
+        p += g.clone();
+        p -= &g;
+        assert_eq!(p, &g * 2u32);
+        p *= 10u32;
+        assert_eq!(p, &g * 20u32);
+        p -= p.clone();
+        assert!(p.is_infinity());
+    }
+
+    #[test]
+    fn test_sum() {
+        let g = ToyPoint::generator();
+        let multiples: Vec<_> = (1u32..=5).map(|k| &g * k).collect();
+        assert_eq!(multiples.iter().sum::<ToyPoint>(), &g * 15u32);
+        assert_eq!(multiples.into_iter().sum::<ToyPoint>(), &g * 15u32);
+        assert_eq!(
+            Vec::<ToyPoint>::new().iter().sum::<ToyPoint>(),
+            ToyPoint::infinity()
+        );
+    }
+
+    #[test]
+    fn test_group_order() {
+        // The order of the group is the order of the generator times the cofactor.
+        let n = Toy::order() * Toy::cofactor();
+        for x in 0u32..223 {
+            for y in 0u32..223 {
+                let p = ToyPoint::new(x, y);
+                if p.is_valid() {
+                    assert_eq!(p * n.clone(), ToyPoint::infinity());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sec() {
+        let g = ToyPoint::generator();
+        assert_eq!(g.to_sec(true), Some(vec![0x03, 47]));
+        for k in 0u32..21 {
+            let p = &g * k;
+            for compressed in [false, true] {
+                assert_eq!(
+                    ToyPoint::from_sec(&p.to_sec(compressed).unwrap()),
+                    Ok(p.clone())
+                );
+            }
+        }
+        assert_eq!(ToyPoint::from_sec(&[0x02, 4]), Err(Error::PointNotOnCurve));
+        assert_eq!(
+            ToyPoint::from_sec(&[0x07]),
+            Err(Error::InvalidPointPrefix(7))
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Discrete logarithms in the multiplicative group of a prime field.
+//!
+//! [`baby_step_giant_step`] takes O(sqrt(n)) time and memory in the order n of the base.
+//! [`pohlig_hellman`] reduces the problem to one such search per prime factor of n, so it is
+//! fast whenever p - 1 is smooth. Neither is feasible for the groups used in cryptography,
+//! which is the point.
+use std::collections::HashMap;
+
+use num_bigint::BigUint;
+
+use crate::finite_field::{FieldMod, FiniteField};
+use crate::number_theory;
+
+/// Finds x in `0..order` with g^x = h, where `order` is the multiplicative order of `g`, or a
+/// multiple of it. Returns `None` if `h` is not a power of `g`.
+///
+/// Stores ceil(sqrt(order)) baby steps g^j, then takes giant steps h * g^(-m*i) until one of
+/// them is in the table.
+///
+/// # Panics
+///
+/// Panics if `g` is zero, or if `order` is so large that the table cannot be indexed.
+pub fn baby_step_giant_step<F: FieldMod>(
+    g: &FiniteField<F>,
+    h: &FiniteField<F>,
+    order: &BigUint,
+) -> Option<BigUint> {
+    let g_inv = g
+        .inverse()
+        .expect("the base of a logarithm must be non-zero");
+    let mut m = order.sqrt();
+    if &m * &m < *order {
+        m += 1u32;
+    }
+    let steps = u64::try_from(&m).expect("group order too large for baby-step giant-step");
+
+    let mut table = HashMap::new();
+    let mut baby = FiniteField::<F>::one();
+    for j in 0..steps {
+        table.entry(baby.num().clone()).or_insert(j);
+        baby = &baby * g;
+    }
+
+    let giant = g_inv.exp(m.clone());
+    let mut gamma = h.clone();
+    for i in 0..steps {
+        if let Some(j) = table.get(gamma.num()) {
+            return Some((&m * i + j) % order);
+        }
+        gamma = &gamma * &giant;
+    }
+    None
+}
+
+/// Finds the smallest x >= 0 with g^x = h, or `None` if `h` is not a power of `g`.
+///
+/// Works modulo each prime power q^e dividing the order n of `g`: the digits of x in base q
+/// are each found by a search in the subgroup of order q, and the results are combined with
+/// the Chinese remainder theorem. This needs the factorization of p - 1, see
+/// [`FiniteField::multiplicative_order`].
+///
+/// # Panics
+///
+/// Panics if `g` is zero.
+pub fn pohlig_hellman<F: FieldMod>(g: &FiniteField<F>, h: &FiniteField<F>) -> Option<BigUint> {
+    let n = g
+        .multiplicative_order()
+        .expect("the base of a logarithm must be non-zero");
+    let g_inv = g.inverse()?;
+
+    let mut congruences = Vec::new();
+    for (q, e) in F::constants().group_factors() {
+        let mut q_e = BigUint::from(1u32);
+        let mut e_n = 0;
+        while e_n < *e && (&n % (&q_e * q)) == 0u32.into() {
+            q_e *= q;
+            e_n += 1;
+        }
+        if e_n == 0 {
+            continue;
+        }
+
+        // gamma generates the subgroup of order q.
+        let gamma = g.exp(&n / q);
+        let mut x = BigUint::from(0u32);
+        let mut q_k = BigUint::from(1u32);
+        for _ in 0..e_n {
+            // Strip the digits found so far and project into the subgroup of order q.
+            let h_k = (&g_inv.exp(x.clone()) * h).exp(&n / (&q_k * q));
+            let d = baby_step_giant_step(&gamma, &h_k, q)?;
+            x += d * &q_k;
+            q_k *= q;
+        }
+        congruences.push((x, q_e));
+    }
+
+    let x = number_theory::crt(&congruences);
+    match g.exp(x.clone()) == *h {
+        true => Some(x),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+    use crate::finite_field::primitive_root;
+
+    define_prime_field!(Field223 = 223u32);
+    define_prime_field!(Field1009 = 1009u32);
+    // p - 1 = 2^5 * 3^2 * 5 * 7 * 11 * 13 * 17 * 19 * 23 is smooth.
+    define_prime_field!(Smooth = 10_708_457_761u64);
+
+    #[test]
+    fn test_baby_step_giant_step() {
+        type F = FiniteField<Field1009>;
+        let g = primitive_root::<Field1009>();
+        let order = BigUint::from(1008u32);
+        for x in [0u32, 1, 2, 500, 1007] {
+            let h = g.exp(x);
+            assert_eq!(baby_step_giant_step(&g, &h, &order), Some(x.into()));
+        }
+        // 4 = 2^2 has order 252 and only generates the squares.
+        let four = F::new(4u32);
+        let order = four.multiplicative_order().unwrap();
+        assert_eq!(baby_step_giant_step(&four, &g, &order), None);
+    }
+
+    #[test]
+    fn test_pohlig_hellman_exhaustive() {
+        type F = FiniteField<Field223>;
+        for g in 1u32..223 {
+            let g = F::new(g);
+            let order = g.multiplicative_order().unwrap();
+            for x in 0u32..50 {
+                let h = g.exp(x);
+                assert_eq!(pohlig_hellman(&g, &h), Some(BigUint::from(x) % &order));
+            }
+        }
+        // 3 is a non-residue, so it is not a power of the residue 4.
+        assert_eq!(pohlig_hellman(&F::new(4u32), &F::new(3u32)), None);
+    }
+
+    #[test]
+    fn test_pohlig_hellman_smooth() {
+        type F = FiniteField<Smooth>;
+        let g = primitive_root::<Smooth>();
+        let x = BigUint::from(1_234_567_890u64);
+        let h = g.exp(x.clone());
+        assert_eq!(pohlig_hellman(&g, &h), Some(x));
+        assert_eq!(pohlig_hellman(&g, &F::one()), Some(0u32.into()));
+    }
+}
@@ -1,35 +1,46 @@
 #![allow(dead_code)]
 
-use std::ops::{Add, Div, Mul, Sub};
+use std::borrow::Borrow;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use num_bigint::BigUint;
 
-pub trait FieldArithmetic:
-    Add<Output = Self>
-    + Sub<Output = Self>
-    + Mul<Output = Self>
-    // + for<'a> Mul<&'a Self, Output = Self>
-    + Div<Output = Self>
-    // + Rem<Output = Self>
-    + Clone
-    + PartialEq
-    + From<u32>
-{
-}
+use crate::algebra::Field;
+use crate::error::{Error, Result};
+use crate::jacobian::Jacobian;
+use crate::ladder::{ladder, ConditionalSelect};
+
+/// A field with a fixed-width big-endian encoding and square roots, which is what the SEC 1
+/// point encoding needs.
+pub trait SecField: Field {
+    /// The number of bytes in an encoded element.
+    fn byte_len() -> usize;
+
+    /// Big-endian encoding, left-padded with zeros to [`SecField::byte_len`] bytes.
+    fn to_bytes_be(&self) -> Vec<u8>;
+
+    /// Decodes an encoding produced by [`SecField::to_bytes_be`].
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self>;
+
+    /// Returns a square root, or `None` if this element is not a quadratic residue.
+    fn sqrt(&self) -> Option<Self>;
 
-impl FieldArithmetic for BigUint {}
-impl FieldArithmetic for u32 {}
-impl FieldArithmetic for u64 {}
-impl FieldArithmetic for u128 {}
+    /// Whether the canonical representative is odd. A compressed point stores this bit of y.
+    fn is_odd(&self) -> bool {
+        self.to_bytes_be().last().is_some_and(|b| b & 1 == 1)
+    }
+}
 
 // Coordinates of a point on the curve
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coordinates<T> {
     pub x: T,
     pub y: T,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PointType<T> {
     Invalid,
     Infinity,
@@ -38,6 +49,7 @@ pub enum PointType<T> {
 
 // An elliptic curve defined by the equation y**2 = x**3 + Ax + B
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EllipticCurve<T> {
     a: T,
     b: T,
@@ -51,7 +63,7 @@ pub struct ECurvePoint<'a, T> {
 
 impl<'a, T> EllipticCurve<T>
 where
-    T: FieldArithmetic,
+    T: Field,
 {
     pub fn new(a: impl Into<T>, b: impl Into<T>) -> Self {
         let a = a.into();
@@ -81,17 +93,177 @@ where
         }
     }
 
+    /// Adds up `points`, owned or borrowed, starting from the point at infinity of this curve,
+    /// so an empty iterator sums to infinity.
+    pub fn sum<P>(&'a self, points: impl IntoIterator<Item = P>) -> ECurvePoint<'a, T>
+    where
+        P: Borrow<ECurvePoint<'a, T>>,
+    {
+        points
+            .into_iter()
+            .fold(self.infinity(), |acc, p| acc + p.borrow())
+    }
+
     pub fn contains(&self, x: &T, y: &T) -> bool {
         let lhs = y.clone() * y.clone();
         let x3 = x.clone() * x.clone() * x.clone();
         let rhs = x3 + self.a.clone() * x.clone() + self.b.clone();
-        return lhs == rhs;
+        lhs == rhs
+    }
+}
+
+/// Encodes a point in SEC 1 form: `0x00` for the point at infinity, `0x04 || x || y`
+/// uncompressed, or `0x02`/`0x03 || x` compressed, where the prefix gives the parity of y.
+/// Returns `None` for an invalid point.
+pub(crate) fn encode_sec<T: SecField>(p: &PointType<T>, compressed: bool) -> Option<Vec<u8>> {
+    match p {
+        PointType::Invalid => None,
+        PointType::Infinity => Some(vec![0x00]),
+        PointType::Point(Coordinates { x, y }) => {
+            let mut bytes = match (compressed, y.is_odd()) {
+                (false, _) => vec![0x04],
+                (true, false) => vec![0x02],
+                (true, true) => vec![0x03],
+            };
+            bytes.extend(x.to_bytes_be());
+            if !compressed {
+                bytes.extend(y.to_bytes_be());
+            }
+            Some(bytes)
+        }
+    }
+}
+
+/// Decodes a SEC 1 encoding of a point on y^2 = x^3 + ax + b. The result is never invalid.
+pub(crate) fn decode_sec<T: SecField>(bytes: &[u8], a: &T, b: &T) -> Result<PointType<T>> {
+    let len = T::byte_len();
+    let (prefix, body) = match bytes.split_first() {
+        Some((prefix, body)) => (*prefix, body),
+        None => {
+            return Err(Error::InvalidLength {
+                expected: 1 + len,
+                actual: 0,
+            })
+        }
+    };
+    let expected = match prefix {
+        0x00 => 1,
+        0x02 | 0x03 => 1 + len,
+        0x04 => 1 + 2 * len,
+        _ => return Err(Error::InvalidPointPrefix(prefix)),
+    };
+    if bytes.len() != expected {
+        return Err(Error::InvalidLength {
+            expected,
+            actual: bytes.len(),
+        });
+    }
+
+    let (x, y) = match prefix {
+        0x00 => return Ok(PointType::Infinity),
+        0x04 => (
+            T::from_bytes_be(&body[..len])?,
+            T::from_bytes_be(&body[len..])?,
+        ),
+        _ => {
+            let x = T::from_bytes_be(body)?;
+            let rhs = x.clone() * x.clone() * x.clone() + a.clone() * x.clone() + b.clone();
+            let odd = prefix == 0x03;
+            let mut y = rhs.sqrt().ok_or(Error::PointNotOnCurve)?;
+            if y.is_odd() != odd {
+                y = T::zero() - y;
+            }
+            // A zero y is its own negation, so it has no odd form.
+            if y.is_odd() != odd {
+                return Err(Error::PointNotOnCurve);
+            }
+            (x, y)
+        }
+    };
+
+    let rhs = x.clone() * x.clone() * x.clone() + a.clone() * x.clone() + b.clone();
+    match y.clone() * y.clone() == rhs {
+        true => Ok(PointType::Point(Coordinates { x, y })),
+        false => Err(Error::PointNotOnCurve),
+    }
+}
+
+impl<'a, T> EllipticCurve<T>
+where
+    T: SecField,
+{
+    /// Decodes a SEC 1 point encoding on this curve: `0x00` for the point at infinity,
+    /// `0x04 || x || y` uncompressed, or `0x02`/`0x03 || x` compressed, where the prefix gives
+    /// the parity of y.
+    pub fn parse_sec(&'a self, bytes: &[u8]) -> Result<ECurvePoint<'a, T>> {
+        let p = decode_sec(bytes, &self.a, &self.b)?;
+        Ok(ECurvePoint { curve: self, p })
+    }
+}
+
+impl<'a, T> ECurvePoint<'a, T>
+where
+    T: SecField,
+{
+    /// The SEC 1 encoding of this point, compressed to the x coordinate and the parity of y if
+    /// `compressed` is set. Returns `None` for an invalid point.
+    pub fn to_sec(&self, compressed: bool) -> Option<Vec<u8>> {
+        encode_sec(&self.p, compressed)
+    }
+}
+
+impl<'a, T> ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    pub fn is_infinity(&self) -> bool {
+        self.p == PointType::Infinity
+    }
+
+    /// Whether this is the point at infinity or a point on the curve.
+    pub fn is_valid(&self) -> bool {
+        self.p != PointType::Invalid
+    }
+
+    /// The affine coordinates, or `None` for the point at infinity and invalid points.
+    pub fn coordinates(&self) -> Option<&Coordinates<T>> {
+        match &self.p {
+            PointType::Point(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        self + self
+    }
+}
+
+impl<'a, T> ECurvePoint<'a, T>
+where
+    T: Field + ConditionalSelect,
+{
+    /// Multiplies this point by a secret scalar in constant time, like
+    /// [`Point::mul_secret`](crate::curve::Point::mul_secret).
+    ///
+    /// `order` is the order of the subgroup the point lies in, such as
+    /// [`SECP256K1_N`](crate::secp256k1::SECP256K1_N) for multiples of `SECP256K1_G`. The
+    /// scalar is reduced modulo it and the ladder runs over its bit length. The curve must have
+    /// odd order, as the complete formulas of the ladder require.
+    pub fn mul_secret(&self, scalar: &BigUint, order: &BigUint) -> Self {
+        if !self.is_valid() {
+            return self.clone();
+        }
+        let scalar = scalar % order;
+        ECurvePoint {
+            curve: self.curve,
+            p: ladder(&self.p, &scalar, order.bits(), &self.curve.a, &self.curve.b),
+        }
     }
 }
 
 impl<'a, T> Add for &ECurvePoint<'a, T>
 where
-    T: FieldArithmetic,
+    T: Field,
 {
     type Output = ECurvePoint<'a, T>;
 
@@ -135,7 +307,7 @@ where
             true => {
                 // Special case: If the y coord is 0, the tangent line is vertical since the elliptic
                 // curve is symmetrical wrt. the x axis. This results on a point on the infinity.
-                if p.y == 0u32.into() {
+                if p.y.is_zero() {
                     return ECurvePoint {
                         curve: self.curve,
                         p: PointType::Infinity,
@@ -161,68 +333,58 @@ where
 // T + T
 impl<'a, T> Add for ECurvePoint<'a, T>
 where
-    T: FieldArithmetic,
+    T: Field,
 {
     type Output = ECurvePoint<'a, T>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        return &self + &rhs;
+        &self + &rhs
     }
 }
 
 // T + &T
 impl<'a, T> Add<&ECurvePoint<'a, T>> for ECurvePoint<'a, T>
 where
-    T: FieldArithmetic,
+    T: Field,
 {
     type Output = ECurvePoint<'a, T>;
 
     fn add(self, rhs: &Self) -> Self::Output {
-        return &self + rhs;
+        &self + rhs
     }
 }
 
 // &T + T
 impl<'a, T> Add<ECurvePoint<'a, T>> for &ECurvePoint<'a, T>
 where
-    T: FieldArithmetic,
+    T: Field,
 {
     type Output = ECurvePoint<'a, T>;
 
     fn add(self, rhs: ECurvePoint<'a, T>) -> Self::Output {
-        return self + &rhs;
+        self + &rhs
     }
 }
 
 // Base implementation: &T * U
+// Variable time. Secret scalars go through `ECurvePoint::mul_secret` instead.
 impl<'a, T, U> Mul<U> for &ECurvePoint<'a, T>
 where
-    T: FieldArithmetic,
-    U: Into<BigUint> + Clone,
+    T: Field,
+    U: Into<BigUint>,
 {
     type Output = ECurvePoint<'a, T>;
 
     fn mul(self, rhs: U) -> Self::Output {
-        let n: BigUint = rhs.into();
-
-        match self.p {
+        match &self.p {
             PointType::Infinity | PointType::Invalid => self.clone(),
-            PointType::Point(_) => {
-                let mut result = self.curve.infinity();
-                let mut current = self.clone();
-                let mut i = n;
-                let zero: BigUint = 0u32.into();
-                let one: BigUint = 1u32.into();
-                let two: BigUint = 2u32.into();
-
-                while i > zero {
-                    if &i % &two == one {
-                        result = &result + &current;
-                    }
-                    current = &current + &current;
-                    i >>= 1;
+            PointType::Point(p) => {
+                // Jacobian coordinates avoid an inversion per addition.
+                let n: BigUint = rhs.into();
+                ECurvePoint {
+                    curve: self.curve,
+                    p: Jacobian::mul(p, &n, &self.curve.a).to_affine(),
                 }
-                result
             }
         }
     }
@@ -231,8 +393,8 @@ where
 // T * U
 impl<'a, T, U> Mul<U> for ECurvePoint<'a, T>
 where
-    T: FieldArithmetic,
-    U: Into<BigUint> + Clone,
+    T: Field,
+    U: Into<BigUint>,
 {
     type Output = ECurvePoint<'a, T>;
 
@@ -241,22 +403,148 @@ where
     }
 }
 
+// T *= U
+impl<'a, T, U> MulAssign<U> for ECurvePoint<'a, T>
+where
+    T: Field,
+    U: Into<BigUint>,
+{
+    fn mul_assign(&mut self, rhs: U) {
+        *self = &*self * rhs;
+    }
+}
+
+// Base implementation: -&T
+impl<'a, T> Neg for &ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = ECurvePoint<'a, T>;
+
+    fn neg(self) -> Self::Output {
+        match &self.p {
+            PointType::Infinity | PointType::Invalid => self.clone(),
+            PointType::Point(c) => ECurvePoint {
+                curve: self.curve,
+                p: PointType::Point(Coordinates {
+                    x: c.x.clone(),
+                    y: T::zero() - c.y.clone(),
+                }),
+            },
+        }
+    }
+}
+
+// -T
+impl<'a, T> Neg for ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = ECurvePoint<'a, T>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+// Base implementation: &T - &T
+impl<'a, T> Sub for &ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = ECurvePoint<'a, T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+// T - T
+impl<'a, T> Sub for ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = ECurvePoint<'a, T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+// T - &T
+impl<'a, T> Sub<&ECurvePoint<'a, T>> for ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = ECurvePoint<'a, T>;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        &self - rhs
+    }
+}
+
+// &T - T
+impl<'a, T> Sub<ECurvePoint<'a, T>> for &ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    type Output = ECurvePoint<'a, T>;
+
+    fn sub(self, rhs: ECurvePoint<'a, T>) -> Self::Output {
+        self - &rhs
+    }
+}
+
+// T += T
+impl<'a, T> AddAssign for ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = &*self + &rhs;
+    }
+}
+
+// T += &T
+impl<'a, T> AddAssign<&ECurvePoint<'a, T>> for ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    fn add_assign(&mut self, rhs: &Self) {
+        *self = &*self + rhs;
+    }
+}
+
+// T -= T
+impl<'a, T> SubAssign for ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = &*self - &rhs;
+    }
+}
+
+// T -= &T
+impl<'a, T> SubAssign<&ECurvePoint<'a, T>> for ECurvePoint<'a, T>
+where
+    T: Field,
+{
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self = &*self - rhs;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::finite_field::{FieldMod, FiniteField};
+    use crate::define_prime_field;
+    use crate::finite_field::FiniteField;
 
     mod finite_field {
         use super::*;
 
-        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-        pub struct Field223Mod;
-
-        impl FieldMod for Field223Mod {
-            fn modulus() -> BigUint {
-                223u32.into()
-            }
-        }
+        define_prime_field!(pub Field223Mod = 223u32);
 
         type Field223 = FiniteField<Field223Mod>;
 
@@ -334,5 +622,155 @@ mod tests {
             dbg!(&result);
             assert_eq!(result, double);
         }
+
+        #[test]
+        fn test_accessors() {
+            let c = test_curve();
+            let p = c.point_at(47u32, 71u32);
+            assert_eq!(
+                p.coordinates(),
+                Some(&Coordinates {
+                    x: 47u32.into(),
+                    y: 71u32.into()
+                })
+            );
+            assert!(!p.is_infinity() && p.is_valid());
+            assert!(c.infinity().is_infinity());
+            assert!(!c.point_at(200u32, 119u32).is_valid());
+            assert_eq!(c.infinity().coordinates(), None);
+            assert_eq!(c.point_at(200u32, 119u32).coordinates(), None);
+            assert_eq!(p.double(), c.point_at(36u32, 111u32));
+        }
+
+        #[test]
+        fn test_neg_sub() {
+            let c = test_curve();
+            let a = c.point_at(192u32, 105u32);
+            let b = c.point_at(17u32, 56u32);
+            assert_eq!(-&a, c.point_at(192u32, 223u32 - 105));
+            assert_eq!(&a + -&a, c.infinity());
+            assert_eq!(-c.infinity(), c.infinity());
+            assert_eq!(&(&a + &b) - &b, a);
+            assert_eq!(a.clone() - a.clone(), c.infinity());
+            assert_eq!(&a - c.infinity(), a);
+        }
+
+        #[test]
+        fn test_assign_ops() {
+            let c = test_curve();
+            let g = c.point_at(47u32, 71u32);
+            let mut p = g.clone();
+            p += &g;
+            assert_eq!(p, &g * 2u32);
+            p += g.clone();
+            p -= &g;
+            assert_eq!(p, &g * 2u32);
+            p *= 10u32;
+            assert_eq!(p, &g * 20u32);
+            p -= p.clone();
+            assert!(p.is_infinity());
+        }
+
+        #[test]
+        fn test_sum() {
+            let c = test_curve();
+            let g = c.point_at(47u32, 71u32);
+            let multiples: Vec<_> = (1u32..=5).map(|k| &g * k).collect();
+            assert_eq!(c.sum(&multiples), &g * 15u32);
+            assert_eq!(c.sum(multiples), &g * 15u32);
+            assert_eq!(c.sum(Vec::<ECurvePoint<_>>::new()), c.infinity());
+        }
+
+        #[test]
+        fn test_sec() {
+            let c = test_curve();
+            let p = c.point_at(47u32, 71u32);
+            assert_eq!(p.to_sec(false), Some(vec![0x04, 47, 71]));
+            assert_eq!(p.to_sec(true), Some(vec![0x03, 47]));
+            assert_eq!(c.infinity().to_sec(true), Some(vec![0x00]));
+            assert_eq!(c.point_at(200u32, 119u32).to_sec(true), None);
+
+            // Every point survives both encodings.
+            let g = c.point_at(15u32, 86u32);
+            for k in 0u32..8 {
+                let q = &g * k;
+                for compressed in [false, true] {
+                    assert_eq!(c.parse_sec(&q.to_sec(compressed).unwrap()), Ok(q.clone()));
+                }
+            }
+        }
+
+        #[test]
+        fn test_parse_sec_errors() {
+            let c = test_curve();
+            assert_eq!(
+                c.parse_sec(&[]),
+                Err(Error::InvalidLength {
+                    expected: 2,
+                    actual: 0
+                })
+            );
+            assert_eq!(c.parse_sec(&[0x05, 47]), Err(Error::InvalidPointPrefix(5)));
+            assert_eq!(
+                c.parse_sec(&[0x04, 47]),
+                Err(Error::InvalidLength {
+                    expected: 3,
+                    actual: 2
+                })
+            );
+            assert_eq!(c.parse_sec(&[0x04, 47, 72]), Err(Error::PointNotOnCurve));
+            // 4^3 + 7 = 71 is not a square mod 223.
+            assert_eq!(c.parse_sec(&[0x02, 4]), Err(Error::PointNotOnCurve));
+            assert!(c.parse_sec(&[0x02, 223]).is_err());
+        }
+    }
+
+    mod small_field {
+        use super::*;
+        use crate::small_field::SmallField;
+
+        type F223 = SmallField<223>;
+
+        fn test_curve() -> EllipticCurve<F223> {
+            EllipticCurve::new(0u32, 7u32)
+        }
+
+        fn all_points(c: &EllipticCurve<F223>) -> Vec<ECurvePoint<'_, F223>> {
+            let mut points = vec![c.infinity()];
+            for x in 0u32..223 {
+                for y in 0u32..223 {
+                    let p = c.point_at(x, y);
+                    if p.p != PointType::Invalid {
+                        points.push(p);
+                    }
+                }
+            }
+            points
+        }
+
+        #[test]
+        fn test_group_order() {
+            let c = test_curve();
+            let points = all_points(&c);
+            assert_eq!(points.len(), 252);
+            for p in &points {
+                assert_eq!(p * 252u32, c.infinity());
+            }
+            assert_eq!(&c.point_at(15u32, 86u32) * 7u32, c.infinity());
+        }
+
+        #[test]
+        fn test_addition_exhaustive() {
+            let c = test_curve();
+            let points = all_points(&c);
+            let q = c.point_at(47u32, 71u32);
+            for p in points {
+                let sum = p + q;
+                assert_ne!(sum.p, PointType::Invalid);
+                assert_eq!(sum, q + p);
+                // Adding a point to itself must agree with scalar multiplication.
+                assert_eq!(p + p, p * 2u32);
+            }
+        }
     }
 }
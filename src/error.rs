@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+use num_bigint::BigUint;
+
+/// Errors returned by the fallible constructors and operations of this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The value is not in the range `0..modulus`.
+    OutOfRange { value: BigUint, modulus: BigUint },
+    /// An encoding does not have the expected number of bytes.
+    InvalidLength { expected: usize, actual: usize },
+    /// A string is not a valid number.
+    Parse(String),
+    /// The operating system entropy source could not be read.
+    Entropy(String),
+    /// A secret cannot be split into `shares` shares with the given threshold.
+    InvalidThreshold { threshold: usize, shares: usize },
+    /// Fewer shares were given than are needed to recover the secret.
+    NotEnoughShares { required: usize, actual: usize },
+    /// A share index is zero or not below the field modulus.
+    InvalidShareIndex(u32),
+    /// Two shares have the same index.
+    DuplicateShare(u32),
+    /// The share with this index does not match the dealer's commitments.
+    InconsistentShare(u32),
+    /// An encoded point starts with a byte that is not a SEC 1 prefix.
+    InvalidPointPrefix(u8),
+    /// Decoded coordinates do not satisfy the curve equation.
+    PointNotOnCurve,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::OutOfRange { value, modulus } => {
+                write!(
+                    f,
+                    "value {} not in field range 0 to {}",
+                    value,
+                    modulus - 1u32
+                )
+            }
+            Error::InvalidLength { expected, actual } => {
+                write!(f, "expected {} bytes, got {}", expected, actual)
+            }
+            Error::Parse(s) => write!(f, "invalid number {:?}", s),
+            Error::Entropy(e) => write!(f, "failed to read entropy: {}", e),
+            Error::InvalidThreshold { threshold, shares } => {
+                write!(f, "invalid threshold {} for {} shares", threshold, shares)
+            }
+            Error::NotEnoughShares { required, actual } => {
+                write!(f, "need {} shares, got {}", required, actual)
+            }
+            Error::InvalidShareIndex(index) => write!(f, "invalid share index {}", index),
+            Error::DuplicateShare(index) => write!(f, "duplicate share with index {}", index),
+            Error::InconsistentShare(index) => {
+                write!(
+                    f,
+                    "share with index {} does not match the commitments",
+                    index
+                )
+            }
+            Error::InvalidPointPrefix(prefix) => {
+                write!(f, "invalid point encoding prefix 0x{:02x}", prefix)
+            }
+            Error::PointNotOnCurve => write!(f, "point is not on the curve"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,508 @@
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+use crate::algebra::{Field, Ring};
+use crate::finite_field::{FieldMod, FiniteField};
+use crate::polynomial::Polynomial;
+
+/// Constants of an extension field F_p[x] / (m(x)), computed once per extension.
+#[derive(Debug)]
+pub struct ExtensionConstants<F: FieldMod> {
+    /// The coefficients of the monic irreducible polynomial m(x), lowest degree first. The
+    /// leading coefficient is 1 and is included.
+    pub modulus: Vec<FiniteField<F>>,
+    /// The degree k of the extension.
+    pub degree: usize,
+    /// The number of elements p^k.
+    pub order: BigUint,
+    /// p^k - 2, the exponent used to invert through Fermat's little theorem.
+    pub order_minus_2: BigUint,
+}
+
+impl<F: FieldMod> ExtensionConstants<F> {
+    /// Builds the constants for the polynomial with the given coefficients, lowest degree
+    /// first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is not monic, has degree below 2, or is not irreducible over
+    /// F_p, see [`Polynomial::is_irreducible`]. A reducible modulus gives a ring with zero
+    /// divisors instead of a field.
+    pub fn new(modulus: Vec<FiniteField<F>>) -> Self {
+        assert!(
+            modulus.len() >= 3,
+            "extension polynomial must have degree at least 2"
+        );
+        assert!(
+            modulus[modulus.len() - 1] == FiniteField::one(),
+            "extension polynomial must be monic"
+        );
+        assert!(
+            Polynomial::new(modulus.clone()).is_irreducible(),
+            "extension polynomial is not irreducible"
+        );
+
+        let degree = modulus.len() - 1;
+        let order = F::modulus().pow(degree as u32);
+        Self {
+            order_minus_2: &order - 2u32,
+            order,
+            degree,
+            modulus,
+        }
+    }
+}
+
+/// The irreducible polynomial that defines an extension of the prime field `F`.
+///
+/// Like [`FieldMod`], implementors build their [`ExtensionConstants`] once, e.g. in a
+/// `lazy_static`, and hand out a `'static` reference to it.
+pub trait ExtensionMod<F: FieldMod>: Clone + PartialEq + 'static {
+    fn constants() -> &'static ExtensionConstants<F>;
+}
+
+/// An element of the extension field F_{p^k} = F_p[x] / (m(x)) in polynomial basis, stored as
+/// its k coefficients, lowest degree first.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ExtensionField<F: FieldMod, E: ExtensionMod<F>> {
+    coeffs: Vec<FiniteField<F>>,
+    _phantom: std::marker::PhantomData<E>,
+}
+
+impl<F: FieldMod, E: ExtensionMod<F>> ExtensionField<F, E> {
+    /// Creates a new element from at most k coefficients, lowest degree first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than k coefficients are given.
+    pub fn new(coeffs: Vec<FiniteField<F>>) -> Self {
+        let degree = E::constants().degree;
+        assert!(
+            coeffs.len() <= degree,
+            "{} coefficients given for an extension of degree {}",
+            coeffs.len(),
+            degree
+        );
+        let mut coeffs = coeffs;
+        coeffs.resize(degree, FiniteField::zero());
+        Self {
+            coeffs,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(vec![])
+    }
+
+    pub fn one() -> Self {
+        Self::new(vec![FiniteField::one()])
+    }
+
+    /// The generator x of the polynomial basis.
+    pub fn x() -> Self {
+        Self::new(vec![FiniteField::zero(), FiniteField::one()])
+    }
+
+    /// The k coefficients of this element, lowest degree first.
+    pub fn coefficients(&self) -> &[FiniteField<F>] {
+        &self.coeffs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|c| c.is_zero())
+    }
+
+    /// Whether this element lies in the prime subfield F_p.
+    pub fn is_base(&self) -> bool {
+        self.coeffs.iter().skip(1).all(|c| c.is_zero())
+    }
+
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    pub fn exp(&self, exponent: impl Into<BigUint>) -> Self {
+        self.exp_ref(&exponent.into())
+    }
+
+    fn exp_ref(&self, exponent: &BigUint) -> Self {
+        let mut result = Self::one();
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = &result * self;
+            }
+        }
+        result
+    }
+
+    /// The Frobenius endomorphism a -> a^p, which fixes exactly the prime subfield.
+    pub fn frobenius(&self) -> Self {
+        self.exp_ref(F::modulus())
+    }
+
+    /// Returns the multiplicative inverse of this element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.exp_ref(&E::constants().order_minus_2)),
+        }
+    }
+
+    /// Computes `self / other`, returning `None` if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inv| self * &inv)
+    }
+}
+
+// &T + &T
+impl<F: FieldMod, E: ExtensionMod<F>> Add for &ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let coeffs = self.coeffs.iter().zip(&other.coeffs).map(|(a, b)| a + b);
+        ExtensionField::new(coeffs.collect())
+    }
+}
+
+// T + T
+impl<F: FieldMod, E: ExtensionMod<F>> Add for ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn add(self, other: Self) -> Self::Output {
+        &self + &other
+    }
+}
+
+// &T - &T
+impl<F: FieldMod, E: ExtensionMod<F>> Sub for &ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        let coeffs = self.coeffs.iter().zip(&other.coeffs).map(|(a, b)| a - b);
+        ExtensionField::new(coeffs.collect())
+    }
+}
+
+// T - T
+impl<F: FieldMod, E: ExtensionMod<F>> Sub for ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        &self - &other
+    }
+}
+
+// &T * &T
+impl<F: FieldMod, E: ExtensionMod<F>> Mul for &ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let constants = E::constants();
+        let k = constants.degree;
+
+        // Schoolbook product of degree at most 2k - 2.
+        let mut product = vec![FiniteField::<F>::zero(); 2 * k - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            if a.is_zero() {
+                continue;
+            }
+            for (j, b) in other.coeffs.iter().enumerate() {
+                product[i + j] += a * b;
+            }
+        }
+
+        // Reduce with x^k = -(m_0 + m_1 x + ... + m_{k-1} x^{k-1}), from the top down.
+        for top in (k..product.len()).rev() {
+            let c = std::mem::replace(&mut product[top], FiniteField::zero());
+            if c.is_zero() {
+                continue;
+            }
+            for (i, m) in constants.modulus[..k].iter().enumerate() {
+                product[top - k + i] -= &c * m;
+            }
+        }
+
+        product.truncate(k);
+        ExtensionField::new(product)
+    }
+}
+
+// T * T
+impl<F: FieldMod, E: ExtensionMod<F>> Mul for ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        &self * &other
+    }
+}
+
+// &T / &T
+impl<F: FieldMod, E: ExtensionMod<F>> Div for &ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.checked_div(other).expect("attempt to divide by zero")
+    }
+}
+
+// T / T
+impl<F: FieldMod, E: ExtensionMod<F>> Div for ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn div(self, other: Self) -> Self::Output {
+        &self / &other
+    }
+}
+
+// -&T
+impl<F: FieldMod, E: ExtensionMod<F>> Neg for &ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn neg(self) -> Self::Output {
+        ExtensionField::new(self.coeffs.iter().map(|c| -c).collect())
+    }
+}
+
+// -T
+impl<F: FieldMod, E: ExtensionMod<F>> Neg for ExtensionField<F, E> {
+    type Output = ExtensionField<F, E>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<F: FieldMod, E: ExtensionMod<F>> Ring for ExtensionField<F, E> {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl<F: FieldMod, E: ExtensionMod<F>> Field for ExtensionField<F, E> {
+    fn inverse(&self) -> Option<Self> {
+        self.inverse()
+    }
+}
+
+impl<F: FieldMod, E: ExtensionMod<F>> From<FiniteField<F>> for ExtensionField<F, E> {
+    fn from(value: FiniteField<F>) -> Self {
+        Self::new(vec![value])
+    }
+}
+
+impl<F: FieldMod, E: ExtensionMod<F>> From<u32> for ExtensionField<F, E> {
+    fn from(value: u32) -> Self {
+        Self::from(FiniteField::from_unreduced(value))
+    }
+}
+
+impl<F: FieldMod, E: ExtensionMod<F>> Display for ExtensionField<F, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(i, c)| match i {
+                0 => format!("{}", c.num()),
+                1 => format!("{}x", c.num()),
+                _ => format!("{}x^{}", c.num(), i),
+            })
+            .collect();
+        let poly = match terms.is_empty() {
+            true => "0".to_string(),
+            false => terms.join(" + "),
+        };
+        write!(
+            f,
+            "FieldElement<{}^{}>({})",
+            F::modulus(),
+            E::constants().degree,
+            poly
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+    use crate::ec::{EllipticCurve, PointType};
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        // x^2 + 1 is irreducible because -1 is not a square mod 7.
+        static ref QUADRATIC7: ExtensionConstants<Field7> =
+            ExtensionConstants::new(vec![1u32.into(), 0u32.into(), 1u32.into()]);
+        // x^3 + 2x + 1 has no roots mod 3, so it is irreducible.
+        static ref CUBIC3: ExtensionConstants<Field3> =
+            ExtensionConstants::new(vec![1u32.into(), 2u32.into(), 0u32.into(), 1u32.into()]);
+    }
+
+    define_prime_field!(Field7 = 7u32);
+    define_prime_field!(Field3 = 3u32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Quadratic7;
+    impl ExtensionMod<Field7> for Quadratic7 {
+        fn constants() -> &'static ExtensionConstants<Field7> {
+            &QUADRATIC7
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Cubic3;
+    impl ExtensionMod<Field3> for Cubic3 {
+        fn constants() -> &'static ExtensionConstants<Field3> {
+            &CUBIC3
+        }
+    }
+
+    type F49 = ExtensionField<Field7, Quadratic7>;
+    type F27 = ExtensionField<Field3, Cubic3>;
+
+    /// Every element of the extension, enumerated through its coefficients.
+    fn elements<F: FieldMod, E: ExtensionMod<F>>() -> Vec<ExtensionField<F, E>> {
+        let p: u32 = F::modulus().try_into().unwrap();
+        let k = E::constants().degree as u32;
+        (0..p.pow(k))
+            .map(|mut n| {
+                let coeffs = (0..k)
+                    .map(|_| {
+                        let c = FiniteField::new(n % p);
+                        n /= p;
+                        c
+                    })
+                    .collect();
+                ExtensionField::new(coeffs)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(Quadratic7::constants().degree, 2);
+        assert_eq!(Quadratic7::constants().order, BigUint::from(49u32));
+        assert_eq!(Cubic3::constants().order, BigUint::from(27u32));
+    }
+
+    #[test]
+    #[should_panic(expected = "monic")]
+    fn test_constants_not_monic() {
+        let _ = ExtensionConstants::<Field7>::new(vec![1u32.into(), 0u32.into(), 2u32.into()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not irreducible")]
+    fn test_constants_reducible() {
+        // x^4 + 1 = (x^2 + 3x + 1)(x^2 + 4x + 1) over F_7, despite having no roots.
+        let _ = ExtensionConstants::<Field7>::new(vec![
+            1u32.into(),
+            0u32.into(),
+            0u32.into(),
+            0u32.into(),
+            1u32.into(),
+        ]);
+    }
+
+    #[test]
+    fn test_mul() {
+        // i^2 = -1
+        let i = F49::x();
+        assert_eq!(i.square(), -F49::one());
+
+        // (1 + 2i)(3 + 4i) = 3 + 10i + 8i^2 = -5 + 10i = 2 + 3i
+        let a = F49::new(vec![1u32.into(), 2u32.into()]);
+        let b = F49::new(vec![3u32.into(), 4u32.into()]);
+        assert_eq!(a * b, F49::new(vec![2u32.into(), 3u32.into()]));
+
+        // x^3 = -2x - 1 = x + 2 in F_27
+        let x = F27::x();
+        assert_eq!(&x * &x.square(), F27::new(vec![2u32.into(), 1u32.into()]));
+    }
+
+    #[test]
+    fn test_field_axioms() {
+        let one = F49::one();
+        for a in elements::<Field7, Quadratic7>() {
+            assert_eq!(&a + &(-&a), F49::zero());
+            match a.inverse() {
+                Some(inv) => assert_eq!(&a * &inv, one),
+                None => assert!(a.is_zero()),
+            }
+            // The multiplicative group has order p^2 - 1.
+            if !a.is_zero() {
+                assert_eq!(a.exp(48u32), one);
+            }
+        }
+
+        for a in elements::<Field3, Cubic3>() {
+            if let Some(inv) = a.inverse() {
+                assert_eq!(&a * &inv, F27::one());
+                assert_eq!(a.exp(26u32), F27::one());
+            }
+        }
+    }
+
+    #[test]
+    fn test_frobenius() {
+        for a in elements::<Field7, Quadratic7>() {
+            assert_eq!(a.frobenius() == a, a.is_base());
+            assert_eq!(a.frobenius().frobenius(), a);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let a = F49::new(vec![3u32.into(), 2u32.into()]);
+        assert_eq!(format!("{}", a), "FieldElement<7^2>(2x + 3)");
+        assert_eq!(format!("{}", F49::zero()), "FieldElement<7^2>(0)");
+    }
+
+    #[test]
+    fn test_curve_over_extension() {
+        // y^2 = x^3 + 3 over F_7 has N1 points (including infinity). Over F_49 the count is
+        // N2 = p^2 + 1 - (t^2 - 2p) with trace t = p + 1 - N1.
+        let base: EllipticCurve<FiniteField<Field7>> = EllipticCurve::new(0u32, 3u32);
+        let base_points = (0u32..7)
+            .flat_map(|x| (0u32..7).map(move |y| (x, y)))
+            .filter(|(x, y)| base.contains(&(*x).into(), &(*y).into()))
+            .count() as i64
+            + 1;
+        let t = 7 + 1 - base_points;
+        let expected = 49 + 1 - (t * t - 2 * 7);
+
+        let curve: EllipticCurve<F49> = EllipticCurve::new(0u32, 3u32);
+        let all = elements::<Field7, Quadratic7>();
+        let mut points = vec![];
+        for x in &all {
+            for y in &all {
+                if curve.contains(x, y) {
+                    points.push((x.clone(), y.clone()));
+                }
+            }
+        }
+        assert_eq!(points.len() as i64 + 1, expected);
+
+        // Every point is killed by the group order.
+        let order = expected as u32;
+        for (x, y) in points.iter().take(10) {
+            let p = curve.point_at(x.clone(), y.clone());
+            assert!(matches!(p.p, PointType::Point(_)));
+            assert_eq!(&p * order, curve.infinity());
+        }
+    }
+}
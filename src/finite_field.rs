@@ -1,14 +1,179 @@
 #![allow(dead_code)]
-use std::fmt::Display;
-use std::ops::{Add, Div, Mul, Sub};
+use std::fmt::{Display, LowerHex, UpperHex};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::algebra::{Field, Ring};
+use crate::ec::SecField;
+use crate::error::{Error, Result};
+use crate::inversion;
+use crate::number_theory;
+use crate::rng::{self, Rng};
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// Constants derived from a prime modulus. They are computed once per field and borrowed by
+/// every operation, see [`FieldMod::constants`].
+#[derive(Debug)]
+pub struct ModulusConstants {
+    /// The prime p.
+    pub modulus: BigUint,
+    /// p - 2, the exponent used to invert through Fermat's little theorem.
+    pub p_minus_2: BigUint,
+    /// (p + 1) / 4, the exponent used to take square roots when p ≡ 3 mod 4.
+    pub sqrt_exp: BigUint,
+    /// (p - 1) / 2, the exponent of Euler's criterion.
+    pub legendre_exp: BigUint,
+    /// The odd part q and the two-adicity s of p - 1 = q * 2^s, used by Tonelli–Shanks.
+    pub odd_part: BigUint,
+    pub two_adicity: u64,
+    /// The smallest quadratic non-residue modulo p.
+    pub non_residue: BigUint,
+    /// The number of bytes needed to encode any element.
+    pub byte_len: usize,
+    /// The prime factorization of p - 1, computed on first use.
+    group_factors: OnceLock<Vec<(BigUint, u32)>>,
+}
+
+impl ModulusConstants {
+    /// Derives the constants of the field of integers modulo `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is not an odd prime, see [`number_theory::is_prime`]. Division in
+    /// a "field" with a composite modulus silently gives wrong answers, so this fails loudly.
+    pub fn new(modulus: impl Into<BigUint>) -> Self {
+        let modulus = modulus.into();
+        assert!(modulus > 2u32.into(), "modulus {} is too small", modulus);
+        assert!(
+            number_theory::is_prime(&modulus),
+            "modulus {} is not prime",
+            modulus
+        );
+
+        let p_minus_1 = &modulus - 1u32;
+        let two_adicity = p_minus_1.trailing_zeros().unwrap_or(0);
+        let legendre_exp = &p_minus_1 >> 1;
+        let mut non_residue = BigUint::from(2u32);
+        while non_residue.modpow(&legendre_exp, &modulus) != p_minus_1 {
+            non_residue += 1u32;
+            assert!(
+                non_residue < modulus,
+                "modulus {} has no quadratic non-residue",
+                modulus
+            );
+        }
+
+        Self {
+            p_minus_2: &modulus - 2u32,
+            sqrt_exp: (&modulus + 1u32) >> 2,
+            odd_part: &p_minus_1 >> two_adicity,
+            two_adicity,
+            legendre_exp,
+            non_residue,
+            byte_len: modulus.bits().div_ceil(8) as usize,
+            group_factors: OnceLock::new(),
+            modulus,
+        }
+    }
+
+    /// The prime factorization of p - 1, the order of the multiplicative group. Factoring is
+    /// only practical for small moduli, see [`number_theory::factorize`].
+    pub fn group_factors(&self) -> &[(BigUint, u32)] {
+        self.group_factors
+            .get_or_init(|| number_theory::factorize(&(&self.modulus - 1u32)))
+    }
+}
+
+/// The modulus of a prime field.
+///
+/// Implementors are expected to build their [`ModulusConstants`] once and hand out a
+/// `'static` reference to it. [`define_prime_field!`] generates such an implementation.
+pub trait FieldMod: Clone + PartialEq + 'static {
+    fn constants() -> &'static ModulusConstants;
+
+    fn modulus() -> &'static BigUint {
+        &Self::constants().modulus
+    }
+}
+
+/// Defines a marker type implementing [`FieldMod`] for the given prime modulus.
+///
+/// The modulus can be any expression convertible into a `BigUint`. It is evaluated, checked
+/// for primality and turned into [`ModulusConstants`] the first time the field is used, and
+/// panics then if the modulus is not prime.
+///
+/// ```
+/// use programming_bitcoin::define_prime_field;
+/// use programming_bitcoin::finite_field::FiniteField;
+///
+/// define_prime_field! {
+///     /// The field of integers modulo 223.
+///     pub Field223 = 223u32
+/// }
+///
+/// let a = FiniteField::<Field223>::new(222u32);
+/// assert_eq!(a + FiniteField::new(1u32), FiniteField::new(0u32));
+/// ```
+#[macro_export]
+macro_rules! define_prime_field {
+    ($(#[$meta:meta])* $vis:vis $name:ident = $modulus:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis struct $name;
+
+        impl $crate::finite_field::FieldMod for $name {
+            fn constants() -> &'static $crate::finite_field::ModulusConstants {
+                static CONSTANTS: ::std::sync::OnceLock<$crate::finite_field::ModulusConstants> =
+                    ::std::sync::OnceLock::new();
+                CONSTANTS.get_or_init(|| $crate::finite_field::ModulusConstants::new($modulus))
+            }
+        }
+    };
+}
+
+impl<F: FieldMod> Ring for FiniteField<F> {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl<F: FieldMod> Field for FiniteField<F> {
+    fn inverse(&self) -> Option<Self> {
+        self.inverse()
+    }
+}
+
+impl<F: FieldMod> SecField for FiniteField<F> {
+    fn byte_len() -> usize {
+        F::constants().byte_len
+    }
+
+    fn to_bytes_be(&self) -> Vec<u8> {
+        self.to_bytes_be()
+    }
 
-use crate::ec::FieldArithmetic;
-use num_bigint::BigUint;
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_be(bytes)
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        self.sqrt()
+    }
 
-pub trait FieldMod: Clone + PartialEq {
-    fn modulus() -> BigUint;
+    fn is_odd(&self) -> bool {
+        self.num.bit(0)
+    }
 }
-impl<F: FieldMod> FieldArithmetic for FiniteField<F> {}
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct FiniteField<F: FieldMod> {
@@ -17,26 +182,278 @@ pub struct FiniteField<F: FieldMod> {
 }
 
 impl<F: FieldMod> FiniteField<F> {
+    /// Creates a new field element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num` is not in the range `0..p`. Use [`FiniteField::try_new`] for values
+    /// coming from untrusted input.
     pub fn new(num: impl Into<BigUint>) -> Self {
+        match Self::try_new(num) {
+            Ok(fe) => fe,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Creates a new field element, failing if `num` is not in the range `0..p`.
+    pub fn try_new(num: impl Into<BigUint>) -> Result<Self> {
         let num = num.into();
         let modulus = F::modulus();
-        assert!(
-            num < modulus,
-            "num {} not in field range 0 to {}",
-            num,
-            &modulus - 1u32
-        );
+        if &num >= modulus {
+            return Err(Error::OutOfRange {
+                value: num,
+                modulus: modulus.clone(),
+            });
+        }
+        Ok(Self::from_reduced(num))
+    }
+
+    /// Creates a new field element from a value the caller knows to be in the range `0..p`.
+    /// The range is only checked in debug builds.
+    pub fn from_reduced(num: BigUint) -> Self {
+        debug_assert!(&num < F::modulus(), "num {} not reduced", num);
         Self {
             num,
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Creates a new field element from an arbitrary integer, reducing it modulo p.
+    pub fn from_unreduced(num: impl Into<BigUint>) -> Self {
+        Self::from_reduced(num.into() % F::modulus())
+    }
+
+    pub(crate) fn num(&self) -> &BigUint {
+        &self.num
+    }
+
+    pub fn zero() -> Self {
+        Self::from_reduced(0u32.into())
+    }
+
+    /// Samples an element uniformly from the whole field.
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self::from_reduced(rng::random_below(rng, F::modulus()))
+    }
+
+    /// Samples an element uniformly from the non-zero elements of the field.
+    pub fn random_nonzero(rng: &mut impl Rng) -> Self {
+        Self::from_reduced(rng::random_below(rng, &(F::modulus() - 1u32)) + 1u32)
+    }
+
+    pub fn one() -> Self {
+        Self::from_reduced(1u32.into())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0u32.into()
+    }
+
+    /// Big-endian encoding, left-padded with zeros to the byte length of the modulus.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let bytes = self.num.to_bytes_be();
+        let mut padded = vec![0u8; F::constants().byte_len - bytes.len()];
+        padded.extend(bytes);
+        padded
+    }
+
+    /// Little-endian encoding, right-padded with zeros to the byte length of the modulus.
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.num.to_bytes_le();
+        bytes.resize(F::constants().byte_len, 0);
+        bytes
+    }
+
+    /// Decodes a big-endian encoding produced by [`FiniteField::to_bytes_be`], failing if the
+    /// length is not the byte length of the modulus or the value is not below p.
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Self> {
+        Self::check_len(bytes)?;
+        Self::try_new(BigUint::from_bytes_be(bytes))
+    }
+
+    /// Decodes a little-endian encoding produced by [`FiniteField::to_bytes_le`], failing if the
+    /// length is not the byte length of the modulus or the value is not below p.
+    pub fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
+        Self::check_len(bytes)?;
+        Self::try_new(BigUint::from_bytes_le(bytes))
+    }
+
+    fn check_len(bytes: &[u8]) -> Result<()> {
+        let expected = F::constants().byte_len;
+        match bytes.len() == expected {
+            true => Ok(()),
+            false => Err(Error::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            }),
+        }
+    }
+
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    pub fn double(&self) -> Self {
+        self + self
+    }
+
+    /// Returns the multiplicative inverse of this element, or `None` if it is zero.
+    ///
+    /// This is [`FiniteField::inverse_vartime`]. Use [`FiniteField::inverse_ct`] when the
+    /// element is secret.
+    pub fn inverse(&self) -> Option<Self> {
+        self.inverse_vartime()
+    }
+
+    /// Inverts with the binary extended Euclidean algorithm. Fast, but its running time
+    /// depends on the value being inverted.
+    pub fn inverse_vartime(&self) -> Option<Self> {
+        inversion::binary_inverse(&self.num, F::modulus()).map(Self::from_reduced)
+    }
+
+    /// Inverts with a constant-time safegcd, whose running time only depends on the modulus.
+    pub fn inverse_ct(&self) -> Option<Self> {
+        inversion::safegcd_inverse(&self.num, F::modulus()).map(Self::from_reduced)
+    }
+
+    /// Computes `self / other`, returning `None` if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inv| self * inv)
+    }
+
     pub fn exp(&self, exponent: impl Into<BigUint>) -> Self {
+        self.exp_ref(&exponent.into())
+    }
+
+    /// Raises this element to a possibly negative power.
+    ///
+    /// By Fermat's little theorem a^(p-1) = 1, so the exponent is first reduced into the range
+    /// `0..p-1`, which turns a^-n into a^(p-1-n).
+    ///
+    /// # Panics
+    ///
+    /// Panics if this element is zero and the exponent is negative.
+    pub fn pow(&self, exponent: impl Into<BigInt>) -> Self {
         let exponent = exponent.into();
+        if self.is_zero() {
+            assert!(
+                exponent.sign() != Sign::Minus,
+                "attempt to raise zero to a negative power"
+            );
+            return self.exp_ref(exponent.magnitude());
+        }
+
+        let order = BigInt::from(F::modulus() - 1u32);
+        let mut reduced = exponent % &order;
+        if reduced.sign() == Sign::Minus {
+            reduced += &order;
+        }
+        self.exp_ref(reduced.magnitude())
+    }
+
+    /// The multiplicative order of this element: the smallest k > 0 with a^k = 1. Returns
+    /// `None` for zero.
+    ///
+    /// Starts from p - 1 and divides out each prime factor q while a^(order/q) stays 1, which
+    /// needs the factorization of p - 1, see [`ModulusConstants::group_factors`].
+    pub fn multiplicative_order(&self) -> Option<BigUint> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut order = F::modulus() - 1u32;
+        for (q, e) in F::constants().group_factors() {
+            for _ in 0..*e {
+                let candidate = &order / q;
+                if self.exp_ref(&candidate).num != 1u32.into() {
+                    break;
+                }
+                order = candidate;
+            }
+        }
+        Some(order)
+    }
+
+    /// Whether this element generates the whole multiplicative group.
+    pub fn is_primitive_root(&self) -> bool {
+        let p_minus_1 = F::modulus() - 1u32;
+        !self.is_zero()
+            && F::constants()
+                .group_factors()
+                .iter()
+                .all(|(q, _)| self.exp_ref(&(&p_minus_1 / q)).num != 1u32.into())
+    }
+
+    /// The Legendre symbol of this element: 1 for non-zero squares, -1 for non-squares and 0
+    /// for zero.
+    pub fn legendre(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+        match self.exp_ref(&F::constants().legendre_exp).num == 1u32.into() {
+            true => 1,
+            false => -1,
+        }
+    }
+
+    /// Whether this element has a square root. Zero is a square.
+    pub fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Returns a square root of this element, or `None` if it is not a quadratic residue.
+    /// The other root is its negation.
+    pub fn sqrt(&self) -> Option<Self> {
+        let constants = F::constants();
+        if self.is_zero() {
+            return Some(self.clone());
+        }
+
+        // When p ≡ 3 mod 4, a^((p+1)/4) is a root whenever one exists.
+        if constants.two_adicity == 1 {
+            let root = self.exp_ref(&constants.sqrt_exp);
+            return match &root * &root == *self {
+                true => Some(root),
+                false => None,
+            };
+        }
+
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        // Tonelli–Shanks. With p - 1 = q * 2^s, keep the invariant r^2 = a * t where t lies in
+        // the subgroup of order 2^m, and shrink m until t = 1.
+        let one = Self::one();
+        let mut m = constants.two_adicity;
+        let mut c = Self::from_reduced(constants.non_residue.clone()).exp_ref(&constants.odd_part);
+        let mut t = self.exp_ref(&constants.odd_part);
+        let mut r = self.exp_ref(&((&constants.odd_part + 1u32) >> 1));
+
+        while t != one {
+            // Find the least i such that t^(2^i) = 1.
+            let mut i = 0;
+            let mut t2i = t.clone();
+            while t2i != one {
+                t2i = &t2i * &t2i;
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = &b * &b;
+            }
+            m = i;
+            c = &b * &b;
+            t = &t * &c;
+            r = &r * &b;
+        }
+        Some(r)
+    }
+
+    fn exp_ref(&self, exponent: &BigUint) -> Self {
         // Modular exponentiation by squaring
         // Handle special cases first
-        if exponent == 0u32.into() {
+        if *exponent == 0u32.into() {
             return Self {
                 num: 1u32.into(),
                 _phantom: std::marker::PhantomData,
@@ -55,21 +472,69 @@ impl<F: FieldMod> FiniteField<F> {
         }
 
         let mut base = self.num.clone();
-        let mut exp = exponent;
         let mut result: BigUint = 1u32.into();
         let modulus = F::modulus();
 
         // Square and multiply algorithm
-        while exp > 0u32.into() {
-            if &exp % 2u32 == 1u32.into() {
-                result = (&result * &base) % &modulus;
+        for i in 0..exponent.bits() {
+            if exponent.bit(i) {
+                result = (&result * &base) % modulus;
             }
-            base = (&base * &base) % &modulus;
-            exp >>= 1;
+            base = (&base * &base) % modulus;
+        }
+
+        Self::from_reduced(result)
+    }
+}
+
+/// Returns the smallest primitive root of F_p, a generator of the multiplicative group.
+pub fn primitive_root<F: FieldMod>() -> FiniteField<F> {
+    let mut g = FiniteField::<F>::one();
+    loop {
+        g = &g + &FiniteField::one();
+        if g.is_primitive_root() {
+            return g;
+        }
+    }
+}
+
+/// Inverts every non-zero element in place using Montgomery's trick: a single field inversion
+/// plus about 3n multiplications for n non-zero elements.
+///
+/// Zero has no inverse, so zero entries are left as zero. Returns the number of zero entries
+/// found, so callers that require every element to be invertible can check for 0.
+pub fn batch_invert<F: FieldMod>(elements: &mut [FiniteField<F>]) -> usize {
+    // prefix[i] holds the product of all non-zero elements before and including elements[i].
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut acc = FiniteField::<F>::one();
+    let mut zeros = 0;
+    for element in elements.iter() {
+        match element.is_zero() {
+            true => zeros += 1,
+            false => acc = &acc * element,
         }
+        prefix.push(acc.clone());
+    }
+    if zeros == elements.len() {
+        return zeros;
+    }
 
-        Self::new(result)
+    // acc_inv is the inverse of the product of all non-zero elements up to index i.
+    let mut acc_inv = acc
+        .inverse()
+        .expect("product of non-zero elements is non-zero");
+    for i in (0..elements.len()).rev() {
+        if elements[i].is_zero() {
+            continue;
+        }
+        let inverse = match i {
+            0 => acc_inv.clone(),
+            _ => &acc_inv * &prefix[i - 1],
+        };
+        acc_inv = &acc_inv * &elements[i];
+        elements[i] = inverse;
     }
+    zeros
 }
 
 // &T + &T
@@ -77,8 +542,12 @@ impl<F: FieldMod> Add for &FiniteField<F> {
     type Output = FiniteField<F>;
 
     fn add(self, other: Self) -> Self::Output {
+        let sum = &self.num + &other.num;
         let modulus = F::modulus();
-        FiniteField::new((&self.num + &other.num) % &modulus)
+        match sum >= *modulus {
+            true => FiniteField::from_reduced(sum - modulus),
+            false => FiniteField::from_reduced(sum),
+        }
     }
 }
 
@@ -114,8 +583,10 @@ impl<F: FieldMod> Sub for &FiniteField<F> {
     type Output = FiniteField<F>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let modulus = F::modulus();
-        FiniteField::new(((&self.num + &modulus) - &rhs.num) % &modulus)
+        match self.num >= rhs.num {
+            true => FiniteField::from_reduced(&self.num - &rhs.num),
+            false => FiniteField::from_reduced((&self.num + F::modulus()) - &rhs.num),
+        }
     }
 }
 
@@ -163,8 +634,7 @@ impl<F: FieldMod> Mul for &FiniteField<F> {
     type Output = FiniteField<F>;
 
     fn mul(self, other: Self) -> Self::Output {
-        let modulus = F::modulus();
-        FiniteField::new((&self.num * &other.num) % &modulus)
+        FiniteField::from_reduced((&self.num * &other.num) % F::modulus())
     }
 }
 
@@ -199,13 +669,7 @@ impl<F: FieldMod> Div for &FiniteField<F> {
     type Output = FiniteField<F>;
 
     fn div(self, other: Self) -> Self::Output {
-        // Using Fermat's Little Theorem:
-        // In a finite field of prime order p, for any number a:
-        // a^(p-1) ≡ 1 (mod p)
-        // Therefore: a^(p-2) is the multiplicative inverse of a
-        let exponent = F::modulus() - 2u32;
-        let inv = other.exp(exponent);
-        self * inv
+        self.checked_div(other).expect("attempt to divide by zero")
     }
 }
 
@@ -235,12 +699,141 @@ impl<F: FieldMod> Div<FiniteField<F>> for &FiniteField<F> {
         self / &other
     }
 }
+
+// -&T
+impl<F: FieldMod> Neg for &FiniteField<F> {
+    type Output = FiniteField<F>;
+
+    fn neg(self) -> Self::Output {
+        match self.is_zero() {
+            true => self.clone(),
+            false => FiniteField::from_reduced(F::modulus() - &self.num),
+        }
+    }
+}
+
+// -T
+impl<F: FieldMod> Neg for FiniteField<F> {
+    type Output = FiniteField<F>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<F: FieldMod> AddAssign<&FiniteField<F>> for FiniteField<F> {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self = &*self + rhs;
+    }
+}
+
+impl<F: FieldMod> AddAssign for FiniteField<F> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
+    }
+}
+
+impl<F: FieldMod> SubAssign<&FiniteField<F>> for FiniteField<F> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self = &*self - rhs;
+    }
+}
+
+impl<F: FieldMod> SubAssign for FiniteField<F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self -= &rhs;
+    }
+}
+
+impl<F: FieldMod> MulAssign<&FiniteField<F>> for FiniteField<F> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<F: FieldMod> MulAssign for FiniteField<F> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self *= &rhs;
+    }
+}
+
+impl<F: FieldMod> DivAssign<&FiniteField<F>> for FiniteField<F> {
+    fn div_assign(&mut self, rhs: &Self) {
+        *self = &*self / rhs;
+    }
+}
+
+impl<F: FieldMod> DivAssign for FiniteField<F> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self /= &rhs;
+    }
+}
+
+impl<F: FieldMod> Sum for FiniteField<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a, F: FieldMod> Sum<&'a FiniteField<F>> for FiniteField<F> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<F: FieldMod> Product for FiniteField<F> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a, F: FieldMod> Product<&'a FiniteField<F>> for FiniteField<F> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
 impl<F: FieldMod> Display for FiniteField<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "FieldElement<{}>({})", F::modulus(), self.num)
     }
 }
 
+impl<F: FieldMod> LowerHex for FiniteField<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        LowerHex::fmt(&self.num, f)
+    }
+}
+
+impl<F: FieldMod> UpperHex for FiniteField<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        UpperHex::fmt(&self.num, f)
+    }
+}
+
+/// Parses a decimal number, or a hexadecimal one when prefixed with `0x`.
+impl<F: FieldMod> FromStr for FiniteField<F> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => (hex, 16),
+            None => (s, 10),
+        };
+        // parse_bytes also accepts a sign and `_` separators, which a canonical encoding must not.
+        let valid = !digits.is_empty()
+            && digits
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (radix == 16 && b.is_ascii_hexdigit()));
+        if !valid {
+            return Err(Error::Parse(s.to_string()));
+        }
+        let num = BigUint::parse_bytes(digits.as_bytes(), radix)
+            .ok_or_else(|| Error::Parse(s.to_string()))?;
+        Self::try_new(num)
+    }
+}
+
 impl<F: FieldMod> From<u32> for FiniteField<F> {
     fn from(value: u32) -> Self {
         Self::new(value)
@@ -251,28 +844,38 @@ impl<F: FieldMod> From<u32> for FiniteField<F> {
 mod tests {
     use super::*;
 
-    #[derive(Debug, Clone, PartialEq)]
-    struct Field7;
-    impl FieldMod for Field7 {
-        fn modulus() -> BigUint {
-            7u32.into()
-        }
-    }
+    define_prime_field!(Field7 = 7u32);
 
-    #[derive(Debug, Clone, PartialEq)]
-    struct Field13;
-    impl FieldMod for Field13 {
-        fn modulus() -> BigUint {
-            13u32.into()
-        }
+    define_prime_field!(Field13 = 13u32);
+
+    define_prime_field!(Field17 = 17u32);
+
+    define_prime_field!(Field19 = 19u32);
+
+    #[test]
+    fn test_modulus_constants() {
+        let c = Field19::constants();
+        assert_eq!(c.modulus, BigUint::from(19u32));
+        assert_eq!(c.p_minus_2, BigUint::from(17u32));
+        assert_eq!(c.sqrt_exp, BigUint::from(5u32));
+        assert_eq!(c.legendre_exp, BigUint::from(9u32));
+        assert_eq!(c.odd_part, BigUint::from(9u32));
+        assert_eq!(c.two_adicity, 1);
+        assert_eq!(c.non_residue, BigUint::from(2u32));
+        assert!(std::ptr::eq(Field19::modulus(), &c.modulus));
+
+        let c = Field13::constants();
+        assert_eq!(c.odd_part, BigUint::from(3u32));
+        assert_eq!(c.two_adicity, 2);
+        assert_eq!(c.non_residue, BigUint::from(2u32));
     }
 
-    #[derive(Debug, Clone, PartialEq)]
-    struct Field19;
-    impl FieldMod for Field19 {
-        fn modulus() -> BigUint {
-            19u32.into()
-        }
+    define_prime_field!(Composite = 221u32);
+
+    #[test]
+    #[should_panic(expected = "modulus 221 is not prime")]
+    fn test_composite_modulus() {
+        let _ = FiniteField::<Composite>::new(1u32);
     }
 
     #[test]
@@ -287,6 +890,109 @@ mod tests {
         let _: FiniteField<Field7> = FiniteField::new(10u32);
     }
 
+    #[test]
+    fn test_try_new() {
+        let fe = FiniteField::<Field7>::try_new(6u32).unwrap();
+        assert_eq!(fe, FiniteField::new(6u32));
+
+        assert_eq!(
+            FiniteField::<Field7>::try_new(7u32),
+            Err(Error::OutOfRange {
+                value: 7u32.into(),
+                modulus: 7u32.into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rng::SeededRng::new(5);
+        let mut seen = [false; 7];
+        for _ in 0..200 {
+            let fe = FiniteField::<Field7>::random(&mut rng);
+            seen[usize::try_from(fe.num()).unwrap()] = true;
+            assert!(!FiniteField::<Field7>::random_nonzero(&mut rng).is_zero());
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+
+    #[test]
+    fn test_bytes() {
+        let fe = FiniteField::<Field19>::new(18u32);
+        assert_eq!(fe.to_bytes_be(), vec![18]);
+        assert_eq!(FiniteField::<Field19>::from_bytes_be(&[18]), Ok(fe.clone()));
+        assert_eq!(
+            FiniteField::<Field19>::from_bytes_le(&fe.to_bytes_le()),
+            Ok(fe)
+        );
+        assert_eq!(
+            FiniteField::<Field19>::from_bytes_be(&[19]),
+            Err(Error::OutOfRange {
+                value: 19u32.into(),
+                modulus: 19u32.into()
+            })
+        );
+        assert_eq!(
+            FiniteField::<Field19>::from_bytes_be(&[0, 1]),
+            Err(Error::InvalidLength {
+                expected: 1,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_bytes_padding() {
+        use crate::secp256k1::Secp256K1Mod;
+
+        let fe = FiniteField::<Secp256K1Mod>::new(0x0102u32);
+        let be = fe.to_bytes_be();
+        let le = fe.to_bytes_le();
+        assert_eq!(be.len(), 32);
+        assert_eq!(be[30..], [1, 2]);
+        assert_eq!(le[..2], [2, 1]);
+        assert!(le[2..].iter().all(|b| *b == 0));
+        assert_eq!(FiniteField::from_bytes_be(&be), Ok(fe.clone()));
+        assert_eq!(FiniteField::from_bytes_le(&le), Ok(fe));
+        assert!(FiniteField::<Secp256K1Mod>::from_bytes_be(&[0xff; 32]).is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("12".parse(), Ok(FiniteField::<Field19>::new(12u32)));
+        assert_eq!("0x12".parse(), Ok(FiniteField::<Field19>::new(18u32)));
+        assert_eq!("0XA".parse(), Ok(FiniteField::<Field19>::new(10u32)));
+        assert_eq!(
+            "19".parse::<FiniteField<Field19>>(),
+            Err(Error::OutOfRange {
+                value: 19u32.into(),
+                modulus: 19u32.into()
+            })
+        );
+        for invalid in [
+            "", "0x", "1a", "-1", "0xg", "+5", "1_0", "1__2", "0xc_8", "0x+f", " 1", "0x_",
+        ] {
+            assert_eq!(
+                invalid.parse::<FiniteField<Field19>>(),
+                Err(Error::Parse(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_hex_format() {
+        let fe = FiniteField::<Field19>::new(15u32);
+        assert_eq!(format!("{:x}", fe), "f");
+        assert_eq!(format!("{:X}", fe), "F");
+        assert_eq!(format!("{:#04x}", fe), "0x0f");
+    }
+
+    #[test]
+    fn test_from_unreduced() {
+        let fe = FiniteField::<Field7>::from_unreduced(23u32);
+        assert_eq!(fe, FiniteField::new(2u32));
+    }
+
     #[test]
     fn test_fe_eq() {
         let a: FiniteField<Field7> = FiniteField::new(5u32);
@@ -407,6 +1113,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pow() {
+        // 7^-1 = 2 mod 13
+        let a: FiniteField<Field13> = FiniteField::new(7u32);
+        assert_eq!(a.pow(-1), FiniteField::new(2u32));
+        assert_eq!(a.pow(-3), FiniteField::new(8u32));
+        assert_eq!(a.pow(3), a.exp(3u32));
+        assert_eq!(a.pow(12), FiniteField::one());
+        assert_eq!(a.pow(-12), FiniteField::one());
+        assert_eq!(a.pow(BigInt::from(-25)), a.pow(-1));
+
+        let zero: FiniteField<Field13> = FiniteField::zero();
+        assert_eq!(zero.pow(0), FiniteField::one());
+        assert_eq!(zero.pow(5), zero);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to raise zero to a negative power")]
+    fn test_pow_zero_negative() {
+        let _ = FiniteField::<Field13>::zero().pow(-1);
+    }
+
+    #[test]
+    fn test_neg() {
+        let a: FiniteField<Field7> = FiniteField::new(3u32);
+        assert_eq!(-&a, FiniteField::new(4u32));
+        assert_eq!(-a.clone() + a, FiniteField::zero());
+        assert_eq!(-FiniteField::<Field7>::zero(), FiniteField::zero());
+    }
+
+    #[test]
+    fn test_square_double() {
+        let a: FiniteField<Field7> = FiniteField::new(5u32);
+        assert_eq!(a.square(), FiniteField::new(4u32));
+        assert_eq!(a.double(), FiniteField::new(3u32));
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut a: FiniteField<Field19> = FiniteField::new(2u32);
+        let b: FiniteField<Field19> = FiniteField::new(7u32);
+
+        a += &b;
+        assert_eq!(a, FiniteField::new(9u32));
+        a -= b.clone();
+        assert_eq!(a, FiniteField::new(2u32));
+        a *= &b;
+        assert_eq!(a, FiniteField::new(14u32));
+        a /= b.clone();
+        assert_eq!(a, FiniteField::new(2u32));
+        a /= &b;
+        assert_eq!(a, FiniteField::new(3u32));
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let elements: Vec<FiniteField<Field7>> = (1u32..7).map(FiniteField::new).collect();
+        assert_eq!(
+            elements.iter().sum::<FiniteField<Field7>>(),
+            FiniteField::zero()
+        );
+        // Wilson's theorem: (p - 1)! = -1 mod p
+        assert_eq!(
+            elements.iter().product::<FiniteField<Field7>>(),
+            -FiniteField::one()
+        );
+        assert_eq!(
+            elements.into_iter().skip(4).sum::<FiniteField<Field7>>(),
+            FiniteField::new(4u32)
+        );
+        assert_eq!(
+            std::iter::empty::<FiniteField<Field7>>().product::<FiniteField<Field7>>(),
+            FiniteField::one()
+        );
+    }
+
     #[test]
     fn test_div_ref() {
         let a: FiniteField<Field19> = FiniteField::new(2u32);
@@ -432,4 +1214,177 @@ mod tests {
         let res = a / b;
         assert_eq!(res.num, exp.num);
     }
+
+    #[test]
+    fn test_inverse() {
+        for n in 1u32..19 {
+            let a: FiniteField<Field19> = FiniteField::new(n);
+            let inv = a.inverse().unwrap();
+            assert_eq!(&a * &inv, FiniteField::new(1u32));
+        }
+        assert_eq!(FiniteField::<Field19>::new(0u32).inverse(), None);
+    }
+
+    #[test]
+    fn test_inverse_variants() {
+        for n in 0u32..13 {
+            let a: FiniteField<Field13> = FiniteField::new(n);
+            let fermat = match n {
+                0 => None,
+                _ => Some(a.exp(11u32)),
+            };
+            assert_eq!(a.inverse_vartime(), fermat);
+            assert_eq!(a.inverse_ct(), fermat);
+        }
+    }
+
+    #[test]
+    fn test_batch_invert() {
+        let values = [3u32, 0, 1, 18, 7, 0, 0, 12];
+        let mut elements: Vec<FiniteField<Field19>> =
+            values.iter().map(|n| FiniteField::new(*n)).collect();
+        assert_eq!(batch_invert(&mut elements), 3);
+        for (n, inv) in values.iter().zip(&elements) {
+            let expected = FiniteField::<Field19>::new(*n)
+                .inverse()
+                .unwrap_or(FiniteField::new(0u32));
+            assert_eq!(*inv, expected);
+        }
+
+        let mut elements: Vec<FiniteField<Field19>> = (1u32..19).map(FiniteField::new).collect();
+        assert_eq!(batch_invert(&mut elements), 0);
+        for (n, inv) in (1u32..19).zip(&elements) {
+            assert_eq!(inv * FiniteField::new(n), FiniteField::new(1u32));
+        }
+    }
+
+    #[test]
+    fn test_batch_invert_degenerate() {
+        let mut empty: Vec<FiniteField<Field7>> = vec![];
+        assert_eq!(batch_invert(&mut empty), 0);
+
+        let mut zeros = vec![FiniteField::<Field7>::new(0u32); 3];
+        assert_eq!(batch_invert(&mut zeros), 3);
+        assert!(zeros.iter().all(|z| z.is_zero()));
+
+        let mut single = vec![FiniteField::<Field7>::new(3u32)];
+        assert_eq!(batch_invert(&mut single), 0);
+        assert_eq!(single[0], FiniteField::new(5u32));
+    }
+
+    #[test]
+    fn test_checked_div() {
+        let a: FiniteField<Field19> = FiniteField::new(2u32);
+        let b: FiniteField<Field19> = FiniteField::new(7u32);
+        let zero: FiniteField<Field19> = FiniteField::new(0u32);
+        assert_eq!(a.checked_div(&b), Some(FiniteField::new(3u32)));
+        assert_eq!(a.checked_div(&zero), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_div_by_zero() {
+        let a: FiniteField<Field19> = FiniteField::new(2u32);
+        let _ = a / FiniteField::new(0u32);
+    }
+
+    /// Checks `legendre`, `is_square` and `sqrt` against every element of the field.
+    fn check_sqrt<F: FieldMod + std::fmt::Debug>() {
+        let p = F::modulus().clone();
+        let mut squares = 0u32;
+        let mut n = BigUint::from(0u32);
+        while n < p {
+            let a = FiniteField::<F>::new(n.clone());
+            let is_square = (0u32..).take_while(|x| BigUint::from(*x) < p).any(|x| {
+                let x = FiniteField::<F>::new(x);
+                &x * &x == a
+            });
+            assert_eq!(a.is_square(), is_square, "is_square({})", a);
+            match a.sqrt() {
+                Some(root) => {
+                    assert!(is_square);
+                    assert_eq!(&root * &root, a);
+                    squares += 1;
+                }
+                None => assert!(!is_square),
+            }
+            let expected = match (a.is_zero(), is_square) {
+                (true, _) => 0,
+                (false, true) => 1,
+                (false, false) => -1,
+            };
+            assert_eq!(a.legendre(), expected);
+            n += 1u32;
+        }
+        // Zero plus (p - 1) / 2 non-zero squares.
+        assert_eq!(BigUint::from(squares), (&p + 1u32) / 2u32);
+    }
+
+    #[test]
+    fn test_sqrt_3_mod_4() {
+        check_sqrt::<Field7>();
+        check_sqrt::<Field19>();
+    }
+
+    #[test]
+    fn test_sqrt_tonelli_shanks() {
+        check_sqrt::<Field13>();
+        // p - 1 = 2^4, so the main loop runs several rounds.
+        check_sqrt::<Field17>();
+    }
+
+    #[test]
+    fn test_sqrt_secp256k1() {
+        use crate::secp256k1::{Secp256K1Mod, SECP256K1_GY};
+
+        let y = FiniteField::<Secp256K1Mod>::from(SECP256K1_GY);
+        let root = (&y * &y).sqrt().unwrap();
+        assert!(root == y || &root + &y == FiniteField::new(0u32));
+        // -1 is not a square when p ≡ 3 mod 4.
+        let minus_one = FiniteField::<Secp256K1Mod>::new(0u32) - FiniteField::new(1u32);
+        assert_eq!(minus_one.sqrt(), None);
+        assert_eq!(minus_one.legendre(), -1);
+    }
+
+    #[test]
+    fn test_multiplicative_order() {
+        type F = FiniteField<Field7>;
+        let orders: Vec<u32> = (1u32..7)
+            .map(|a| {
+                F::new(a)
+                    .multiplicative_order()
+                    .unwrap()
+                    .try_into()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(orders, vec![1, 3, 6, 3, 6, 2]);
+        assert_eq!(F::zero().multiplicative_order(), None);
+
+        // Exactly phi(d) elements of F_13* have order d, for each d dividing 12.
+        let mut counts = [0; 13];
+        for a in 1u32..13 {
+            let order: usize = FiniteField::<Field13>::new(a)
+                .multiplicative_order()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            counts[order] += 1;
+        }
+        assert_eq!(counts, [0, 1, 1, 2, 2, 0, 2, 0, 0, 0, 0, 0, 4]);
+    }
+
+    #[test]
+    fn test_primitive_root() {
+        assert_eq!(primitive_root::<Field7>(), FiniteField::new(3u32));
+        assert_eq!(primitive_root::<Field13>(), FiniteField::new(2u32));
+        assert_eq!(primitive_root::<Field17>(), FiniteField::new(3u32));
+        assert_eq!(primitive_root::<Field19>(), FiniteField::new(2u32));
+        assert!(!FiniteField::<Field7>::new(2u32).is_primitive_root());
+        assert!(!FiniteField::<Field7>::zero().is_primitive_root());
+        assert_eq!(
+            Field13::constants().group_factors(),
+            &[(2u32.into(), 2), (3u32.into(), 1)]
+        );
+    }
 }
@@ -0,0 +1,285 @@
+//! Modular inversion modulo an odd modulus.
+//!
+//! [`binary_inverse`] is a variable-time binary extended Euclidean algorithm and is the fastest
+//! choice for public values. [`safegcd_inverse`] runs the Bernstein–Yang divstep iteration a
+//! fixed number of times on fixed-width limbs, selecting with masks instead of branching, so
+//! its running time only depends on the size of the modulus.
+use num_bigint::BigUint;
+
+/// Inverts `a` modulo the odd modulus `p` in variable time. Returns `None` if `a` is not
+/// invertible, e.g. zero.
+pub fn binary_inverse(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    assert!(p.bit(0), "modulus {} is not odd", p);
+    let a = a % p;
+    if a == 0u32.into() {
+        return None;
+    }
+
+    // Invariants: x1 * a ≡ u (mod p) and x2 * a ≡ v (mod p).
+    let one = BigUint::from(1u32);
+    let (mut u, mut v) = (a, p.clone());
+    let (mut x1, mut x2) = (one.clone(), BigUint::from(0u32));
+
+    let halve = |x: &mut BigUint| {
+        if x.bit(0) {
+            *x += p;
+        }
+        *x >>= 1;
+    };
+
+    while u != one && v != one {
+        // gcd(a, p) > 1 eventually makes u and v equal, and then u zero.
+        if u == 0u32.into() {
+            return None;
+        }
+        while !u.bit(0) {
+            u >>= 1;
+            halve(&mut x1);
+        }
+        while !v.bit(0) {
+            v >>= 1;
+            halve(&mut x2);
+        }
+        if u >= v {
+            u -= &v;
+            x1 = match x1 >= x2 {
+                true => x1 - &x2,
+                false => x1 + p - &x2,
+            };
+        } else {
+            v -= &u;
+            x2 = match x2 >= x1 {
+                true => x2 - &x1,
+                false => x2 + p - &x1,
+            };
+        }
+    }
+
+    match u == one {
+        true => Some(x1),
+        false => Some(x2),
+    }
+}
+
+/// Inverts `a` modulo the odd modulus `p` with a constant-time safegcd. Returns `None` if `a`
+/// is not invertible, e.g. zero.
+///
+/// The divstep loop runs a number of iterations that depends only on the bit length of `p`
+/// and touches every limb on every iteration. Converting from and to `BigUint` is not constant
+/// time.
+pub fn safegcd_inverse(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    assert!(p.bit(0), "modulus {} is not odd", p);
+    let bits = p.bits();
+    // One extra limb holds the sign of f and g and the carry of d + e before reduction.
+    let limbs = (bits / 64 + 1) as usize;
+    let modulus = to_limbs(p, limbs);
+
+    // Invariants: d * a ≡ f (mod p) and e * a ≡ g (mod p), with f odd.
+    let mut f = modulus.clone();
+    let mut g = to_limbs(&(a % p), limbs);
+    let mut d = vec![0u64; limbs];
+    let mut e = vec![0u64; limbs];
+    e[0] = 1;
+    let mut delta: i64 = 1;
+
+    // Iteration bound from Bernstein and Yang, "Fast constant-time gcd computation and modular
+    // inversion", theorem 11.2.
+    let iterations = match bits < 46 {
+        true => (49 * bits + 80) / 17,
+        false => (49 * bits + 57) / 17,
+    };
+
+    for _ in 0..iterations {
+        let g_odd = 0u64.wrapping_sub(g[0] & 1);
+        let swap = ((delta.wrapping_neg() >> 63) as u64) & g_odd;
+
+        // If delta > 0 and g is odd: (delta, f, g, d, e) <- (-delta, g, -f, e, -d).
+        cswap(swap, &mut f, &mut g);
+        cswap(swap, &mut d, &mut e);
+        cneg(swap, &mut g);
+        cneg_mod(swap, &mut e, &modulus);
+        let s = swap as i64;
+        delta = (delta ^ s) - s;
+
+        // (delta, g, e) <- (1 + delta, (g + [g odd] f) / 2, (e + [g odd] d) / 2).
+        delta += 1;
+        cadd(g_odd, &mut g, &f);
+        cadd_mod(g_odd, &mut e, &d, &modulus);
+        shr1(&mut g, true);
+        half_mod(&mut e, &modulus);
+    }
+
+    // Now g = 0 and f = ±gcd(a, p).
+    let negative = 0u64.wrapping_sub(f[limbs - 1] >> 63);
+    cneg(negative, &mut f);
+    if f[0] != 1 || f[1..].iter().any(|l| *l != 0) {
+        return None;
+    }
+    cneg_mod(negative, &mut d, &modulus);
+    Some(from_limbs(&d))
+}
+
+fn to_limbs(value: &BigUint, len: usize) -> Vec<u64> {
+    let mut limbs = vec![0u64; len];
+    for (limb, digit) in limbs.iter_mut().zip(value.iter_u64_digits()) {
+        *limb = digit;
+    }
+    limbs
+}
+
+fn from_limbs(limbs: &[u64]) -> BigUint {
+    let digits: Vec<u32> = limbs
+        .iter()
+        .flat_map(|l| [*l as u32, (*l >> 32) as u32])
+        .collect();
+    BigUint::from_slice(&digits)
+}
+
+/// Swaps `a` and `b` if `mask` is all ones.
+fn cswap(mask: u64, a: &mut [u64], b: &mut [u64]) {
+    for (a, b) in a.iter_mut().zip(b.iter_mut()) {
+        let t = (*a ^ *b) & mask;
+        *a ^= t;
+        *b ^= t;
+    }
+}
+
+/// Adds `b` to `a` if `mask` is all ones, wrapping around the fixed width.
+fn cadd(mask: u64, a: &mut [u64], b: &[u64]) -> u64 {
+    let mut carry = 0u64;
+    for (a, b) in a.iter_mut().zip(b) {
+        let (s, c1) = a.overflowing_add(b & mask);
+        let (s, c2) = s.overflowing_add(carry);
+        *a = s;
+        carry = (c1 | c2) as u64;
+    }
+    carry
+}
+
+/// Negates the two's complement value `a` if `mask` is all ones.
+fn cneg(mask: u64, a: &mut [u64]) {
+    let mut carry = mask & 1;
+    for a in a.iter_mut() {
+        let (s, c) = (*a ^ mask).overflowing_add(carry);
+        *a = s;
+        carry = c as u64;
+    }
+}
+
+/// Shifts `a` right by one bit, replicating the sign bit if `signed`.
+fn shr1(a: &mut [u64], signed: bool) {
+    let top = a[a.len() - 1];
+    let mut high = match signed {
+        true => top & (1 << 63),
+        false => 0,
+    };
+    for a in a.iter_mut().rev() {
+        let low = *a & 1;
+        *a = (*a >> 1) | high;
+        high = low << 63;
+    }
+}
+
+/// Subtracts `p` from `a` if `a >= p`, for `a < 2p`.
+fn reduce_once(a: &mut [u64], p: &[u64]) {
+    let mut s = a.to_vec();
+    let mut borrow = 0u64;
+    for (s, p) in s.iter_mut().zip(p) {
+        let (d, b1) = s.overflowing_sub(*p);
+        let (d, b2) = d.overflowing_sub(borrow);
+        *s = d;
+        borrow = (b1 | b2) as u64;
+    }
+    let keep = 0u64.wrapping_sub(borrow ^ 1);
+    for (a, s) in a.iter_mut().zip(&s) {
+        *a = (s & keep) | (*a & !keep);
+    }
+}
+
+/// Computes `a + b mod p` in place if `mask` is all ones, for `a, b < p`.
+fn cadd_mod(mask: u64, a: &mut [u64], b: &[u64], p: &[u64]) {
+    cadd(mask, a, b);
+    reduce_once(a, p);
+}
+
+/// Computes `-a mod p` in place if `mask` is all ones, for `a < p`.
+fn cneg_mod(mask: u64, a: &mut [u64], p: &[u64]) {
+    let mut negated = p.to_vec();
+    let mut minus_a = a.to_vec();
+    cneg(u64::MAX, &mut minus_a);
+    cadd(u64::MAX, &mut negated, &minus_a);
+    // p - 0 = p must map back to zero.
+    reduce_once(&mut negated, p);
+    for (a, n) in a.iter_mut().zip(&negated) {
+        *a = (n & mask) | (*a & !mask);
+    }
+}
+
+/// Computes `a / 2 mod p` in place, for `a < p` and odd `p`.
+fn half_mod(a: &mut [u64], p: &[u64]) {
+    let odd = 0u64.wrapping_sub(a[0] & 1);
+    cadd(odd, a, p);
+    shr1(a, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fermat_inverse(a: &BigUint, p: &BigUint) -> BigUint {
+        a.modpow(&(p - 2u32), p)
+    }
+
+    #[test]
+    fn test_small_primes_exhaustive() {
+        for p in [3u32, 5, 7, 13, 17, 19, 223, 1009] {
+            let p = BigUint::from(p);
+            let mut a = BigUint::from(1u32);
+            while a < p {
+                let expected = fermat_inverse(&a, &p);
+                assert_eq!(binary_inverse(&a, &p), Some(expected.clone()));
+                assert_eq!(safegcd_inverse(&a, &p), Some(expected));
+                a += 1u32;
+            }
+            assert_eq!(binary_inverse(&0u32.into(), &p), None);
+            assert_eq!(safegcd_inverse(&0u32.into(), &p), None);
+        }
+    }
+
+    #[test]
+    fn test_large_primes() {
+        let secp = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let mersenne = (BigUint::from(1u32) << 127) - 1u32;
+        for p in [secp, mersenne] {
+            let mut a = BigUint::from(0x2545F4914F6CDD1Du64);
+            for _ in 0..32 {
+                a = (&a * &a + 12345u32) % &p;
+                let expected = fermat_inverse(&a, &p);
+                assert_eq!(binary_inverse(&a, &p), Some(expected.clone()));
+                assert_eq!(safegcd_inverse(&a, &p), Some(expected));
+            }
+            let minus_one = &p - 1u32;
+            assert_eq!(binary_inverse(&minus_one, &p), Some(minus_one.clone()));
+            assert_eq!(safegcd_inverse(&minus_one, &p), Some(minus_one));
+        }
+    }
+
+    #[test]
+    fn test_non_units() {
+        let n = BigUint::from(15u32);
+        for a in [3u32, 5, 6, 10, 12] {
+            assert_eq!(binary_inverse(&a.into(), &n), None);
+            assert_eq!(safegcd_inverse(&a.into(), &n), None);
+        }
+        for a in [2u32, 4, 7, 8] {
+            let a = BigUint::from(a);
+            let inv = binary_inverse(&a, &n).unwrap();
+            assert_eq!((&a * &inv) % &n, 1u32.into());
+            assert_eq!(safegcd_inverse(&a, &n), Some(inv));
+        }
+    }
+}
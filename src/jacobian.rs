@@ -0,0 +1,427 @@
+//! Points in Jacobian coordinates.
+//!
+//! The triple (X, Y, Z) stands for the affine point (X / Z^2, Y / Z^3), and any triple with
+//! Z = 0 for the point at infinity. Addition and doubling then need no field inversion, which
+//! costs as much as a full exponentiation; only the conversion back to affine coordinates does.
+//! Scalar multiplication of [`ECurvePoint`] and [`Point`] runs in these coordinates.
+//!
+//! The addition and doubling formulas are not complete. The exceptional cases are handled
+//! with branches: infinity on either side, equal points, which fall back to doubling, and
+//! opposite points, which give infinity. The results are correct for every input, but the
+//! running time depends on the points. [`ProjectivePoint`] uses complete formulas instead.
+//!
+//! [`ECurvePoint`]: crate::ec::ECurvePoint
+//! [`ProjectivePoint`]: crate::projective::ProjectivePoint
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul};
+
+use num_bigint::BigUint;
+
+use crate::algebra::Field;
+use crate::curve::{CurveParams, Point};
+use crate::ec::{Coordinates, PointType};
+use crate::scalar_mul::straus;
+
+/// The arithmetic behind [`JacobianPoint`], for a curve given only by its coefficient a.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Jacobian<T> {
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T: Field> Jacobian<T> {
+    pub(crate) fn infinity() -> Self {
+        Self {
+            x: T::one(),
+            y: T::one(),
+            z: T::zero(),
+        }
+    }
+
+    pub(crate) fn from_affine(p: &Coordinates<T>) -> Self {
+        Self {
+            x: p.x.clone(),
+            y: p.y.clone(),
+            z: T::one(),
+        }
+    }
+
+    pub(crate) fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// Converts back to affine coordinates with one inversion.
+    pub(crate) fn to_affine(&self) -> PointType<T> {
+        match self.z.inverse() {
+            None => PointType::Infinity,
+            Some(z_inv) => {
+                let z_inv2 = z_inv.clone() * z_inv.clone();
+                PointType::Point(Coordinates {
+                    x: self.x.clone() * z_inv2.clone(),
+                    y: self.y.clone() * z_inv2 * z_inv,
+                })
+            }
+        }
+    }
+
+    /// Doubles the point on the curve with coefficient `a`.
+    pub(crate) fn double(&self, a: &T) -> Self {
+        // A vertical tangent at y = 0 gives the point at infinity.
+        if self.is_infinity() || self.y.is_zero() {
+            return Self::infinity();
+        }
+        let xx = self.x.clone() * self.x.clone();
+        let yy = self.y.clone() * self.y.clone();
+        let zz = self.z.clone() * self.z.clone();
+
+        // S = 4 X Y^2, M = 3 X^2 + a Z^4
+        let s = twice(twice(self.x.clone() * yy.clone()));
+        let m = twice(xx.clone()) + xx + a.clone() * zz.clone() * zz;
+
+        let x = m.clone() * m.clone() - twice(s.clone());
+        let yyyy = yy.clone() * yy;
+        let y = m * (s - x.clone()) - twice(twice(twice(yyyy)));
+        let z = twice(self.y.clone() * self.z.clone());
+        Self { x, y, z }
+    }
+
+    /// Adds two points on the curve with coefficient `a`. Equal and opposite points are handled,
+    /// so this is correct for every pair of inputs.
+    pub(crate) fn add(&self, other: &Self, a: &T) -> Self {
+        if self.is_infinity() {
+            return other.clone();
+        }
+        if other.is_infinity() {
+            return self.clone();
+        }
+        let z1z1 = self.z.clone() * self.z.clone();
+        let z2z2 = other.z.clone() * other.z.clone();
+        let u1 = self.x.clone() * z2z2.clone();
+        let u2 = other.x.clone() * z1z1.clone();
+        let s1 = self.y.clone() * other.z.clone() * z2z2;
+        let s2 = other.y.clone() * self.z.clone() * z1z1;
+        let z = self.z.clone() * other.z.clone();
+        Self::add_normalized(u1, s1, u2, s2, z, || self.double(a))
+    }
+
+    /// Adds an affine point, which saves the multiplications by its Z = 1.
+    pub(crate) fn add_affine(&self, other: &Coordinates<T>, a: &T) -> Self {
+        if self.is_infinity() {
+            return Self::from_affine(other);
+        }
+        let z1z1 = self.z.clone() * self.z.clone();
+        let u2 = other.x.clone() * z1z1.clone();
+        let s2 = other.y.clone() * self.z.clone() * z1z1;
+        let (u1, s1, z) = (self.x.clone(), self.y.clone(), self.z.clone());
+        Self::add_normalized(u1, s1, u2, s2, z, || self.double(a))
+    }
+
+    /// Finishes an addition once both points are scaled to the common denominator `z`:
+    /// (u1, s1) and (u2, s2) are their X and Y multiplied by the other point's Z^2 and Z^3.
+    fn add_normalized(u1: T, s1: T, u2: T, s2: T, z: T, doubled: impl FnOnce() -> Self) -> Self {
+        if u1 == u2 {
+            return match s1 == s2 {
+                true => doubled(),
+                false => Self::infinity(),
+            };
+        }
+        let h = u2 - u1.clone();
+        let r = s2 - s1.clone();
+        let hh = h.clone() * h.clone();
+        let hhh = h.clone() * hh.clone();
+        let v = u1 * hh;
+
+        let x = r.clone() * r.clone() - hhh.clone() - twice(v.clone());
+        let y = r * (v - x.clone()) - s1 * hhh;
+        Self { x, y, z: z * h }
+    }
+
+    /// Adds the affine point `p`, or subtracts it if `negative` is set.
+    pub(crate) fn add_signed(&self, p: &PointType<T>, negative: bool, a: &T) -> Self {
+        match p {
+            PointType::Point(c) if negative => self.add_affine(
+                &Coordinates {
+                    x: c.x.clone(),
+                    y: T::zero() - c.y.clone(),
+                },
+                a,
+            ),
+            PointType::Point(c) => self.add_affine(c, a),
+            _ => self.clone(),
+        }
+    }
+
+    /// Multiplies an affine point by `n` with a width-5 NAF: one addition per six bits on
+    /// average, against one per two bits for double-and-add.
+    pub(crate) fn mul(p: &Coordinates<T>, n: &BigUint, a: &T) -> Self {
+        straus(&[(n, p)], a)
+    }
+
+    /// Whether both triples stand for the same affine point, compared without inverting.
+    pub(crate) fn equals(&self, other: &Self) -> bool {
+        match (self.is_infinity(), other.is_infinity()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1z1 = self.z.clone() * self.z.clone();
+                let z2z2 = other.z.clone() * other.z.clone();
+                self.x.clone() * z2z2.clone() == other.x.clone() * z1z1.clone()
+                    && self.y.clone() * other.z.clone() * z2z2
+                        == other.y.clone() * self.z.clone() * z1z1
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Converts many points to affine coordinates with a single field inversion, using
+/// Montgomery's trick as in [`batch_invert`](crate::finite_field::batch_invert).
+pub(crate) fn batch_to_affine<T: Field>(points: &[Jacobian<T>]) -> Vec<PointType<T>> {
+    // prefix[i] holds the product of the non-zero Z before points[i].
+    let mut prefix = Vec::with_capacity(points.len());
+    let mut acc = T::one();
+    for p in points {
+        prefix.push(acc.clone());
+        if !p.is_infinity() {
+            acc = acc * p.z.clone();
+        }
+    }
+
+    // Every Z is non-zero in the product, so it is invertible.
+    let mut acc_inv = acc.inverse().expect("product of non-zero elements");
+    let mut affine = vec![PointType::Infinity; points.len()];
+    for (i, p) in points.iter().enumerate().rev() {
+        if p.is_infinity() {
+            continue;
+        }
+        let z_inv = acc_inv.clone() * prefix[i].clone();
+        acc_inv = acc_inv * p.z.clone();
+        let z_inv2 = z_inv.clone() * z_inv.clone();
+        affine[i] = PointType::Point(Coordinates {
+            x: p.x.clone() * z_inv2.clone(),
+            y: p.y.clone() * z_inv2 * z_inv,
+        });
+    }
+    affine
+}
+
+fn twice<T: Field>(x: T) -> T {
+    x.clone() + x
+}
+
+/// A point on the curve `C` in Jacobian coordinates.
+pub struct JacobianPoint<C: CurveParams> {
+    inner: Jacobian<C::Field>,
+    _curve: PhantomData<fn() -> C>,
+}
+
+impl<C: CurveParams> JacobianPoint<C> {
+    fn wrap(inner: Jacobian<C::Field>) -> Self {
+        Self {
+            inner,
+            _curve: PhantomData,
+        }
+    }
+
+    pub fn infinity() -> Self {
+        Self::wrap(Jacobian::infinity())
+    }
+
+    pub fn generator() -> Self {
+        Self::wrap(Jacobian::from_affine(&C::generator()))
+    }
+
+    /// Converts an affine point, returning `None` if it is invalid.
+    pub fn from_affine(p: &Point<C>) -> Option<Self> {
+        match &p.p {
+            PointType::Invalid => None,
+            PointType::Infinity => Some(Self::infinity()),
+            PointType::Point(c) => Some(Self::wrap(Jacobian::from_affine(c))),
+        }
+    }
+
+    /// Converts to affine coordinates, which costs one field inversion.
+    pub fn to_affine(&self) -> Point<C> {
+        Point::from_type(self.inner.to_affine())
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.inner.is_infinity()
+    }
+
+    pub fn double(&self) -> Self {
+        Self::wrap(self.inner.double(&C::a()))
+    }
+
+    /// Adds a point given in affine coordinates.
+    pub fn add_affine(&self, other: &Coordinates<C::Field>) -> Self {
+        Self::wrap(self.inner.add_affine(other, &C::a()))
+    }
+}
+
+// Implemented by hand so that `C` itself needs none of these traits.
+impl<C: CurveParams> Clone for JacobianPoint<C> {
+    fn clone(&self) -> Self {
+        Self::wrap(self.inner.clone())
+    }
+}
+
+impl<C: CurveParams> Copy for JacobianPoint<C> where C::Field: Copy {}
+
+/// Two points are equal if they stand for the same affine point, whatever their Z.
+impl<C: CurveParams> PartialEq for JacobianPoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+}
+
+impl<C: CurveParams> Eq for JacobianPoint<C> where C::Field: Eq {}
+
+impl<C: CurveParams> Debug for JacobianPoint<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JacobianPoint")
+            .field("x", &self.inner.x)
+            .field("y", &self.inner.y)
+            .field("z", &self.inner.z)
+            .finish()
+    }
+}
+
+impl<C: CurveParams> From<JacobianPoint<C>> for Point<C> {
+    fn from(p: JacobianPoint<C>) -> Self {
+        p.to_affine()
+    }
+}
+
+impl<C: CurveParams> Add for &JacobianPoint<C> {
+    type Output = JacobianPoint<C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        JacobianPoint::wrap(self.inner.add(&rhs.inner, &C::a()))
+    }
+}
+
+// T + T
+impl<C: CurveParams> Add for JacobianPoint<C> {
+    type Output = JacobianPoint<C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+// T + &T
+impl<C: CurveParams> Add<&JacobianPoint<C>> for JacobianPoint<C> {
+    type Output = JacobianPoint<C>;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        &self + rhs
+    }
+}
+
+// &T + T
+impl<C: CurveParams> Add<JacobianPoint<C>> for &JacobianPoint<C> {
+    type Output = JacobianPoint<C>;
+
+    fn add(self, rhs: JacobianPoint<C>) -> Self::Output {
+        self + &rhs
+    }
+}
+
+// Base implementation: &T * U
+impl<C: CurveParams, U: Into<BigUint>> Mul<U> for &JacobianPoint<C> {
+    type Output = JacobianPoint<C>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        let n: BigUint = rhs.into();
+        let a = C::a();
+        let mut result = Jacobian::infinity();
+        for i in (0..n.bits()).rev() {
+            result = result.double(&a);
+            if n.bit(i) {
+                result = result.add(&self.inner, &a);
+            }
+        }
+        JacobianPoint::wrap(result)
+    }
+}
+
+// T * U
+impl<C: CurveParams, U: Into<BigUint>> Mul<U> for JacobianPoint<C> {
+    type Output = JacobianPoint<C>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        &self * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::test_curves::{Field97, Toy97};
+
+    type ToyPoint = Point<Toy97>;
+    type ToyJacobian = JacobianPoint<Toy97>;
+
+    fn multiples() -> Vec<ToyPoint> {
+        let g = ToyPoint::generator();
+        let mut points = vec![ToyPoint::infinity()];
+        for _ in 1..101 {
+            points.push(points.last().unwrap() + &g);
+        }
+        points
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for p in multiples() {
+            assert_eq!(ToyJacobian::from_affine(&p).unwrap().to_affine(), p);
+        }
+        assert_eq!(ToyJacobian::from_affine(&ToyPoint::new(1u32, 1u32)), None);
+    }
+
+    #[test]
+    fn test_add_matches_affine() {
+        let points = multiples();
+        let jacobian: Vec<_> = points
+            .iter()
+            .map(|p| ToyJacobian::from_affine(p).unwrap())
+            .collect();
+        for (p, jp) in points.iter().zip(&jacobian) {
+            assert_eq!(jp.double().to_affine(), p + p);
+            for (q, jq) in points.iter().zip(&jacobian) {
+                let expected = p + q;
+                assert_eq!((jp + jq).to_affine(), expected);
+                if let PointType::Point(c) = &q.p {
+                    assert_eq!(jp.add_affine(c).to_affine(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_equality() {
+        let g = ToyJacobian::generator();
+        let lambda = Field97::new(5u32);
+        let scaled = ToyJacobian::wrap(Jacobian {
+            x: g.inner.x.clone() * lambda.clone() * lambda.clone(),
+            y: g.inner.y.clone() * lambda.clone() * lambda.clone() * lambda.clone(),
+            z: lambda,
+        });
+        assert_eq!(scaled, g);
+        assert_ne!(scaled, g.double());
+        assert_ne!(scaled, ToyJacobian::infinity());
+        assert_eq!(scaled.to_affine(), ToyPoint::generator());
+    }
+
+    #[test]
+    fn test_mul() {
+        let g = ToyJacobian::generator();
+        let points = multiples();
+        for k in 0u32..101 {
+            assert_eq!((&g * k).to_affine(), points[k as usize]);
+        }
+        assert!((g * Toy97::order()).is_infinity());
+    }
+}
@@ -0,0 +1,126 @@
+//! Constant-time scalar multiplication for secret scalars.
+//!
+//! Double-and-add and wNAF branch on the bits of the scalar, and their addition formulas branch
+//! on the points, so their running time leaks the scalar. The Montgomery ladder here performs
+//! one addition and one doubling for every bit of the group order, whatever the scalar, and
+//! swaps its two accumulators with masks instead of branching. Multiples of a fixed base are
+//! faster with the constant-time [`FixedBaseTable`](crate::scalar_mul::FixedBaseTable).
+//!
+//! Both operations use the complete formulas of [`projective`](crate::projective). They are
+//! correct for every pair of inputs on a curve of odd order, including equal points and the
+//! point at infinity, so there are no special cases to branch on. The whole multiplication is
+//! only as constant time as the field arithmetic, which holds for
+//! [`Secp256K1Field`](crate::secp256k1_field::Secp256K1Field) but not for the `BigUint` based
+//! fields.
+//!
+//! Secret scalars should go through [`Point::mul_secret`], [`ECurvePoint::mul_secret`] or
+//! [`secp256k1::public_key`](crate::secp256k1::public_key); the `Mul` operators are variable
+//! time.
+//!
+//! [`ECurvePoint::mul_secret`]: crate::ec::ECurvePoint::mul_secret
+use num_bigint::BigUint;
+
+use crate::algebra::Field;
+use crate::curve::{CurveParams, Point};
+use crate::ec::PointType;
+use crate::projective::Projective;
+
+/// Selection between two values without branching on the choice.
+pub trait ConditionalSelect: Sized {
+    /// Returns `a` if `choice` is 0 and `b` if it is 1.
+    fn conditional_select(a: &Self, b: &Self, choice: u64) -> Self;
+
+    /// Swaps `a` and `b` if `choice` is 1 and leaves them if it is 0.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: u64) {
+        let new_a = Self::conditional_select(a, b, choice);
+        let new_b = Self::conditional_select(b, a, choice);
+        *a = new_a;
+        *b = new_b;
+    }
+}
+
+/// Computes `scalar * p` on y^2 = x^3 + ax + b with a Montgomery ladder over `bits` bits.
+///
+/// The scalar must be below 2^`bits`, and `p` must not be invalid.
+pub(crate) fn ladder<T: Field + ConditionalSelect>(
+    p: &PointType<T>,
+    scalar: &BigUint,
+    bits: u64,
+    a: &T,
+    b: &T,
+) -> PointType<T> {
+    let b3 = b.clone() + b.clone() + b.clone();
+    let base = Projective::from_point_type(p);
+
+    // Read the bits from fixed-width limbs so the loop does not depend on the scalar's length.
+    let mut limbs = scalar.to_u64_digits();
+    limbs.resize(bits.div_ceil(64) as usize, 0);
+
+    // Invariant: r1 = r0 + P.
+    let mut r0 = Projective::infinity();
+    let mut r1 = base;
+    for i in (0..bits as usize).rev() {
+        let bit = (limbs[i / 64] >> (i % 64)) & 1;
+        Projective::conditional_swap(&mut r0, &mut r1, bit);
+        r1 = r0.add(&r1, a, &b3);
+        r0 = r0.double(a, &b3);
+        Projective::conditional_swap(&mut r0, &mut r1, bit);
+    }
+    r0.to_affine()
+}
+
+impl<C: CurveParams> Point<C>
+where
+    C::Field: ConditionalSelect,
+{
+    /// Multiplies this point by a secret scalar in constant time, with a Montgomery ladder over
+    /// the bit length of the order of `C`.
+    ///
+    /// The point must lie in the subgroup generated by the generator, since the scalar is first
+    /// reduced modulo its order. That reduction works on `BigUint` and is only constant time for
+    /// scalars already below the order, such as private keys.
+    pub fn mul_secret(&self, scalar: &BigUint) -> Self {
+        if !self.is_valid() {
+            return self.clone();
+        }
+        let order = C::order();
+        let scalar = scalar % &order;
+        Point::from_type(ladder(&self.p, &scalar, order.bits(), &C::a(), &C::b()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SeededRng;
+    use crate::secp256k1::{random_scalar, Secp256K1Point, SECP256K1_N};
+    use crate::secp256k1_field::Secp256K1Field;
+
+    #[test]
+    fn test_conditional_swap() {
+        let (mut a, mut b) = (Secp256K1Field::from(1u32), Secp256K1Field::from(2u32));
+        Secp256K1Field::conditional_swap(&mut a, &mut b, 0);
+        assert_eq!((a, b), (1u32.into(), 2u32.into()));
+        Secp256K1Field::conditional_swap(&mut a, &mut b, 1);
+        assert_eq!((a, b), (2u32.into(), 1u32.into()));
+    }
+
+    #[test]
+    fn test_mul_secret() {
+        let g = Secp256K1Point::generator();
+        let mut rng = SeededRng::new(23);
+        let mut scalars: Vec<BigUint> = (0u32..4).map(BigUint::from).collect();
+        scalars.extend((0..4).map(|_| random_scalar(&mut rng)));
+        scalars.push(&*SECP256K1_N - 1u32);
+        for k in scalars {
+            assert_eq!(g.mul_secret(&k), g * k);
+        }
+        assert_eq!(g.mul_secret(&SECP256K1_N), Secp256K1Point::infinity());
+        assert_eq!(
+            Secp256K1Point::infinity().mul_secret(&5u32.into()),
+            Secp256K1Point::infinity()
+        );
+        let invalid = Secp256K1Point::new(1u32, 1u32);
+        assert!(!invalid.mul_secret(&5u32.into()).is_valid());
+    }
+}
@@ -1,4 +1,31 @@
+pub mod algebra;
+pub mod binary_curve;
+pub mod binary_field;
+pub mod curve;
+pub mod discrete_log;
 pub mod ec;
+pub mod error;
+pub mod extension_field;
 pub mod finite_field;
+pub mod inversion;
+pub mod jacobian;
+pub mod ladder;
+pub mod mod_ring;
+pub mod montgomery;
+pub mod number_theory;
+pub mod polynomial;
+pub mod projective;
+pub mod rng;
+pub mod scalar_mul;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod shamir;
+pub mod small_field;
 
 pub mod secp256k1;
+pub mod secp256k1_field;
+pub mod sect163k1;
+
+// Re-exported for the macros, which expand in crates that may not depend on it.
+#[doc(hidden)]
+pub use num_bigint;
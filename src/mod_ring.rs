@@ -0,0 +1,323 @@
+//! The ring Z/nZ of integers modulo an arbitrary modulus.
+//!
+//! Unlike [`FiniteField`](crate::finite_field::FiniteField) the modulus does not have to be
+//! prime, so not every non-zero element is invertible. That makes [`ModRing`] a [`Ring`] but
+//! not a [`Field`](crate::algebra::Field), and it is where RSA-style arithmetic and
+//! factoring experiments live.
+use std::fmt::Display;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+use crate::algebra::Ring;
+use crate::number_theory;
+
+/// The modulus of a ring of integers modulo n.
+///
+/// Implementors are expected to hand out a `'static` reference to the modulus.
+/// [`define_ring_modulus!`] generates such an implementation.
+pub trait RingMod: Clone + PartialEq + 'static {
+    fn modulus() -> &'static BigUint;
+}
+
+/// Defines a marker type implementing [`RingMod`] for the given modulus.
+///
+/// The modulus can be any expression convertible into a `BigUint` and is evaluated the first
+/// time the ring is used. It must be greater than one.
+///
+/// ```
+/// use programming_bitcoin::define_ring_modulus;
+/// use programming_bitcoin::mod_ring::ModRing;
+///
+/// define_ring_modulus! {
+///     /// The integers modulo 15.
+///     pub Mod15 = 15u32
+/// }
+///
+/// let a = ModRing::<Mod15>::new(7u32);
+/// assert_eq!(a.inverse(), Some(ModRing::new(13u32)));
+/// assert_eq!(ModRing::<Mod15>::new(5u32).inverse(), None);
+/// ```
+#[macro_export]
+macro_rules! define_ring_modulus {
+    ($(#[$meta:meta])* $vis:vis $name:ident = $modulus:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        $vis struct $name;
+
+        impl $crate::mod_ring::RingMod for $name {
+            fn modulus() -> &'static $crate::num_bigint::BigUint {
+                static MODULUS: ::std::sync::OnceLock<$crate::num_bigint::BigUint> =
+                    ::std::sync::OnceLock::new();
+                MODULUS.get_or_init(|| $crate::mod_ring::checked_modulus($modulus))
+            }
+        }
+    };
+}
+
+/// Converts `modulus` for [`define_ring_modulus!`].
+///
+/// # Panics
+///
+/// Panics if `modulus` is not greater than one.
+#[doc(hidden)]
+pub fn checked_modulus(modulus: impl Into<BigUint>) -> BigUint {
+    let modulus = modulus.into();
+    assert!(modulus > 1u32.into(), "modulus {} is too small", modulus);
+    modulus
+}
+
+/// An element of Z/nZ, always fully reduced below n.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ModRing<M: RingMod> {
+    num: BigUint,
+    _phantom: std::marker::PhantomData<M>,
+}
+
+impl<M: RingMod> ModRing<M> {
+    /// Creates a new element, reducing `num` modulo n.
+    pub fn new(num: impl Into<BigUint>) -> Self {
+        Self::from_reduced(num.into() % M::modulus())
+    }
+
+    fn from_reduced(num: BigUint) -> Self {
+        Self {
+            num,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// The canonical representative of this element, in `0..n`.
+    pub fn value(&self) -> &BigUint {
+        &self.num
+    }
+
+    pub fn zero() -> Self {
+        Self::from_reduced(0u32.into())
+    }
+
+    pub fn one() -> Self {
+        Self::from_reduced(1u32.into())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0u32.into()
+    }
+
+    pub fn exp(&self, exponent: impl Into<BigUint>) -> Self {
+        Self::from_reduced(self.num.modpow(&exponent.into(), M::modulus()))
+    }
+
+    /// Whether this element has a multiplicative inverse, i.e. is coprime to n.
+    pub fn is_unit(&self) -> bool {
+        self.gcd() == 1u32.into()
+    }
+
+    /// The greatest common divisor of this element and n. When an inversion fails this is a
+    /// non-trivial factor of n, unless the element is zero, which is how ECM finds factors.
+    pub fn gcd(&self) -> BigUint {
+        number_theory::gcd(&self.num, M::modulus())
+    }
+
+    /// Returns the multiplicative inverse of this element, or `None` if it is not a unit.
+    pub fn inverse(&self) -> Option<Self> {
+        number_theory::mod_inverse(&self.num, M::modulus()).map(Self::from_reduced)
+    }
+
+    /// Computes `self / other`, returning `None` if `other` is not a unit.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inv| self * &inv)
+    }
+}
+
+impl<M: RingMod> Ring for ModRing<M> {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+// &T + &T
+impl<M: RingMod> Add for &ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let sum = &self.num + &other.num;
+        match &sum >= M::modulus() {
+            true => ModRing::from_reduced(sum - M::modulus()),
+            false => ModRing::from_reduced(sum),
+        }
+    }
+}
+
+// T + T
+impl<M: RingMod> Add for ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn add(self, other: Self) -> Self::Output {
+        &self + &other
+    }
+}
+
+// &T - &T
+impl<M: RingMod> Sub for &ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        match self.num >= other.num {
+            true => ModRing::from_reduced(&self.num - &other.num),
+            false => ModRing::from_reduced(M::modulus() - &other.num + &self.num),
+        }
+    }
+}
+
+// T - T
+impl<M: RingMod> Sub for ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        &self - &other
+    }
+}
+
+// &T * &T
+impl<M: RingMod> Mul for &ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        ModRing::from_reduced((&self.num * &other.num) % M::modulus())
+    }
+}
+
+// T * T
+impl<M: RingMod> Mul for ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        &self * &other
+    }
+}
+
+impl<M: RingMod> Neg for &ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn neg(self) -> Self::Output {
+        &ModRing::zero() - self
+    }
+}
+
+impl<M: RingMod> Neg for ModRing<M> {
+    type Output = ModRing<M>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<M: RingMod> From<u32> for ModRing<M> {
+    fn from(value: u32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<M: RingMod> Display for ModRing<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RingElement<{}>({})", M::modulus(), self.num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+    use crate::finite_field::FiniteField;
+
+    define_ring_modulus!(Mod15 = 15u32);
+    define_ring_modulus!(Mod16 = 16u32);
+    // Textbook RSA with p = 61 and q = 53.
+    define_ring_modulus!(Rsa3233 = 3233u32);
+    define_ring_modulus!(Phi3120 = 3120u32);
+    define_prime_field!(Field7 = 7u32);
+
+    type Z15 = ModRing<Mod15>;
+
+    /// Computes x^n by repeated multiplication through the `Ring` interface alone.
+    fn ring_pow<R: Ring>(x: &R, n: u32) -> R {
+        (0..n).fold(R::one(), |acc, _| acc * x.clone())
+    }
+
+    #[test]
+    fn test_units() {
+        for a in 0u32..15 {
+            let a = Z15::new(a);
+            match a.inverse() {
+                Some(inv) => {
+                    assert!(a.is_unit());
+                    assert_eq!(&a * &inv, Z15::one());
+                }
+                None => {
+                    assert!(!a.is_unit());
+                    assert_ne!(a.gcd(), 1u32.into());
+                }
+            }
+        }
+        let units = (0u32..15).filter(|a| Z15::new(*a).is_unit()).count();
+        assert_eq!(units, 8);
+        assert_eq!(Z15::new(6u32).gcd(), 3u32.into());
+    }
+
+    #[test]
+    fn test_even_modulus() {
+        let a = ModRing::<Mod16>::new(7u32);
+        assert_eq!(a.inverse(), Some(ModRing::new(7u32)));
+        assert_eq!(ModRing::<Mod16>::new(4u32).inverse(), None);
+        assert_eq!(-ModRing::<Mod16>::new(3u32), ModRing::new(13u32));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (a, b) = (Z15::new(9u32), Z15::new(11u32));
+        assert_eq!(&a + &b, Z15::new(5u32));
+        assert_eq!(&a - &b, Z15::new(13u32));
+        assert_eq!(&a * &b, Z15::new(9u32));
+        assert_eq!(
+            Z15::new(7u32).checked_div(&Z15::new(2u32)),
+            Some(Z15::new(11u32))
+        );
+        assert_eq!(Z15::new(7u32).checked_div(&Z15::new(3u32)), None);
+        assert_eq!(Z15::new(100u32), Z15::new(10u32));
+    }
+
+    #[test]
+    fn test_rsa() {
+        let d = ModRing::<Phi3120>::new(17u32).inverse().unwrap();
+        assert_eq!(d.value(), &2753u32.into());
+
+        let m = ModRing::<Rsa3233>::new(65u32);
+        let c = m.exp(17u32);
+        assert_eq!(c, ModRing::new(2790u32));
+        assert_eq!(c.exp(d.value().clone()), m);
+    }
+
+    #[test]
+    fn test_generic_over_ring() {
+        assert_eq!(ring_pow(&Z15::new(2u32), 4), Z15::one());
+        assert_eq!(
+            ring_pow(&FiniteField::<Field7>::new(3u32), 6),
+            FiniteField::one()
+        );
+        assert!(<Z15 as Ring>::zero().is_zero());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Z15::new(4u32).to_string(), "RingElement<15>(4)");
+    }
+}
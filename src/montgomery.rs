@@ -0,0 +1,518 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Sub};
+
+use num_bigint::BigUint;
+
+use crate::algebra::{Field, Ring};
+use crate::finite_field::{FieldMod, FiniteField};
+
+/// Constants for Montgomery arithmetic modulo an odd prime that fits in `N` 64-bit limbs,
+/// with R = 2^(64 * N).
+#[derive(Debug)]
+pub struct MontgomeryParams<const N: usize> {
+    /// The prime p as little-endian limbs.
+    pub modulus: [u64; N],
+    /// R^2 mod p, used to move values into Montgomery form.
+    pub r2: [u64; N],
+    /// R mod p, the Montgomery form of one.
+    pub one: [u64; N],
+    /// -p^-1 mod 2^64.
+    pub inv: u64,
+}
+
+impl<const N: usize> MontgomeryParams<N> {
+    pub fn new(modulus: &BigUint) -> Self {
+        assert!(modulus.bit(0), "modulus {} is not odd", modulus);
+        assert!(
+            modulus.bits() <= 64 * N as u64,
+            "modulus {} does not fit in {} limbs",
+            modulus,
+            N
+        );
+
+        let r = BigUint::from(1u32) << (64 * N);
+        let p = to_limbs::<N>(modulus);
+
+        // Newton iteration doubles the number of correct low bits each step: 1 -> 64 in 6.
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p[0].wrapping_mul(inv)));
+        }
+
+        Self {
+            modulus: p,
+            r2: to_limbs(&((&r * &r) % modulus)),
+            one: to_limbs(&(r % modulus)),
+            inv: inv.wrapping_neg(),
+        }
+    }
+}
+
+/// A prime modulus with precomputed Montgomery constants.
+///
+/// Like [`FieldMod::constants`], implementors build the [`MontgomeryParams`] once, e.g. in a
+/// `lazy_static`, and hand out a `'static` reference to it.
+pub trait MontgomeryMod<const N: usize>: FieldMod {
+    fn montgomery() -> &'static MontgomeryParams<N>;
+}
+
+/// An element of the prime field `F` stored in Montgomery form `a * R mod p` as `N` limbs.
+///
+/// Multiplication uses word-by-word Montgomery reduction instead of a `BigUint` division, and
+/// no operation allocates. Convert from and to [`FiniteField`] with `From`.
+#[derive(PartialEq, Eq)]
+pub struct MontgomeryField<F: MontgomeryMod<N>, const N: usize> {
+    limbs: [u64; N],
+    _phantom: std::marker::PhantomData<F>,
+}
+
+// Implemented by hand so that the marker type does not need to be `Copy`.
+impl<F: MontgomeryMod<N>, const N: usize> Clone for MontgomeryField<F, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> Copy for MontgomeryField<F, N> {}
+
+fn to_limbs<const N: usize>(value: &BigUint) -> [u64; N] {
+    let mut limbs = [0u64; N];
+    for (limb, digit) in limbs.iter_mut().zip(value.iter_u64_digits()) {
+        *limb = digit;
+    }
+    limbs
+}
+
+fn from_limbs<const N: usize>(limbs: &[u64; N]) -> BigUint {
+    let digits: Vec<u32> = limbs
+        .iter()
+        .flat_map(|l| [*l as u32, (*l >> 32) as u32])
+        .collect();
+    BigUint::from_slice(&digits)
+}
+
+/// Subtracts `b` from `a` in place, returning the borrow out.
+fn sub_assign_limbs<const N: usize>(a: &mut [u64; N], b: &[u64; N]) -> u64 {
+    let mut borrow = 0u64;
+    for (a, b) in a.iter_mut().zip(b) {
+        let (d, b1) = a.overflowing_sub(*b);
+        let (d, b2) = d.overflowing_sub(borrow);
+        *a = d;
+        borrow = (b1 | b2) as u64;
+    }
+    borrow
+}
+
+/// Adds `b` to `a` in place, returning the carry out.
+fn add_assign_limbs<const N: usize>(a: &mut [u64; N], b: &[u64; N]) -> u64 {
+    let mut carry = 0u64;
+    for (a, b) in a.iter_mut().zip(b) {
+        let (s, c1) = a.overflowing_add(*b);
+        let (s, c2) = s.overflowing_add(carry);
+        *a = s;
+        carry = (c1 | c2) as u64;
+    }
+    carry
+}
+
+/// Subtracts p from `a` if `a >= p` or if `carry` (the bit above the top limb) is set.
+fn reduce_once<const N: usize>(a: [u64; N], carry: u64, p: &[u64; N]) -> [u64; N] {
+    let mut s = a;
+    let borrow = sub_assign_limbs(&mut s, p);
+    let mask = 0u64.wrapping_sub(carry | (borrow ^ 1));
+    let mut r = a;
+    for (r, s) in r.iter_mut().zip(&s) {
+        *r = (s & mask) | (*r & !mask);
+    }
+    r
+}
+
+/// Computes `a * b * R^-1 mod p` with the coarsely integrated operand scanning (CIOS) method.
+fn mont_mul<const N: usize>(a: &[u64; N], b: &[u64; N], params: &MontgomeryParams<N>) -> [u64; N] {
+    let p = &params.modulus;
+    // The accumulator has two extra words above the N limbs.
+    let mut t = [0u64; N];
+    let mut t_n = 0u64;
+
+    for b_i in b {
+        let mut carry: u128 = 0;
+        for (t_j, a_j) in t.iter_mut().zip(a) {
+            let v = *t_j as u128 + (*a_j as u128) * (*b_i as u128) + carry;
+            *t_j = v as u64;
+            carry = v >> 64;
+        }
+        let v = t_n as u128 + carry;
+        t_n = v as u64;
+        let t_n1 = (v >> 64) as u64;
+
+        // Add m * p so the lowest word becomes zero, then shift everything down one word.
+        let m = t[0].wrapping_mul(params.inv);
+        let mut carry = (t[0] as u128 + (m as u128) * (p[0] as u128)) >> 64;
+        for j in 1..N {
+            let v = t[j] as u128 + (m as u128) * (p[j] as u128) + carry;
+            t[j - 1] = v as u64;
+            carry = v >> 64;
+        }
+        let v = t_n as u128 + carry;
+        t[N - 1] = v as u64;
+        t_n = t_n1 + (v >> 64) as u64;
+    }
+
+    reduce_once(t, t_n, p)
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> MontgomeryField<F, N> {
+    fn from_montgomery_limbs(limbs: [u64; N]) -> Self {
+        Self {
+            limbs,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a new field element from a value in the range `0..p`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num` is not in the range `0..p`.
+    pub fn new(num: impl Into<BigUint>) -> Self {
+        Self::from(&FiniteField::<F>::new(num))
+    }
+
+    pub fn zero() -> Self {
+        Self::from_montgomery_limbs([0u64; N])
+    }
+
+    pub fn one() -> Self {
+        Self::from_montgomery_limbs(F::montgomery().one)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|l| *l == 0)
+    }
+
+    /// The canonical (non-Montgomery) value of this element.
+    pub fn to_biguint(&self) -> BigUint {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        from_limbs(&mont_mul(&self.limbs, &one, F::montgomery()))
+    }
+
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    pub fn exp(&self, exponent: impl Into<BigUint>) -> Self {
+        self.exp_ref(&exponent.into())
+    }
+
+    fn exp_ref(&self, exponent: &BigUint) -> Self {
+        let mut result = Self::one();
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = &result * self;
+            }
+        }
+        result
+    }
+
+    /// Returns the multiplicative inverse of this element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.exp_ref(&F::constants().p_minus_2)),
+        }
+    }
+
+    /// Computes `self / other`, returning `None` if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inv| self * &inv)
+    }
+}
+
+// T + T
+impl<F: MontgomeryMod<N>, const N: usize> Add for MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let mut r = self.limbs;
+        let carry = add_assign_limbs(&mut r, &other.limbs);
+        Self::from_montgomery_limbs(reduce_once(r, carry, &F::montgomery().modulus))
+    }
+}
+
+// &T + &T
+impl<F: MontgomeryMod<N>, const N: usize> Add for &MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn add(self, other: Self) -> Self::Output {
+        *self + *other
+    }
+}
+
+// T - T
+impl<F: MontgomeryMod<N>, const N: usize> Sub for MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        let mut r = self.limbs;
+        if sub_assign_limbs(&mut r, &other.limbs) == 1 {
+            add_assign_limbs(&mut r, &F::montgomery().modulus);
+        }
+        Self::from_montgomery_limbs(r)
+    }
+}
+
+// &T - &T
+impl<F: MontgomeryMod<N>, const N: usize> Sub for &MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        *self - *other
+    }
+}
+
+// T * T
+impl<F: MontgomeryMod<N>, const N: usize> Mul for MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::from_montgomery_limbs(mont_mul(&self.limbs, &other.limbs, F::montgomery()))
+    }
+}
+
+// &T * &T
+impl<F: MontgomeryMod<N>, const N: usize> Mul for &MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        *self * *other
+    }
+}
+
+// T / T
+impl<F: MontgomeryMod<N>, const N: usize> Div for MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.checked_div(&other).expect("attempt to divide by zero")
+    }
+}
+
+// &T / &T
+impl<F: MontgomeryMod<N>, const N: usize> Div for &MontgomeryField<F, N> {
+    type Output = MontgomeryField<F, N>;
+
+    fn div(self, other: Self) -> Self::Output {
+        *self / *other
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> Ring for MontgomeryField<F, N> {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> Field for MontgomeryField<F, N> {
+    fn inverse(&self) -> Option<Self> {
+        self.inverse()
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> From<u32> for MontgomeryField<F, N> {
+    fn from(value: u32) -> Self {
+        Self::from(&FiniteField::<F>::from_unreduced(value))
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> From<&FiniteField<F>> for MontgomeryField<F, N> {
+    fn from(value: &FiniteField<F>) -> Self {
+        let params = F::montgomery();
+        Self::from_montgomery_limbs(mont_mul(&to_limbs(value.num()), &params.r2, params))
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> From<FiniteField<F>> for MontgomeryField<F, N> {
+    fn from(value: FiniteField<F>) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> From<&MontgomeryField<F, N>> for FiniteField<F> {
+    fn from(value: &MontgomeryField<F, N>) -> Self {
+        FiniteField::from_reduced(value.to_biguint())
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> From<MontgomeryField<F, N>> for FiniteField<F> {
+    fn from(value: MontgomeryField<F, N>) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> Debug for MontgomeryField<F, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MontgomeryField({})", self.to_biguint())
+    }
+}
+
+impl<F: MontgomeryMod<N>, const N: usize> Display for MontgomeryField<F, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&FiniteField::<F>::from(self), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+    use crate::ec::{EllipticCurve, PointType};
+    use crate::secp256k1::{Secp256K1Mod, SECP256K1_G, SECP256K1_GX, SECP256K1_GY};
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref FIELD223_MONT: MontgomeryParams<1> = MontgomeryParams::new(&223u32.into());
+        static ref MERSENNE127_MONT: MontgomeryParams<2> =
+            MontgomeryParams::new(Mersenne127::modulus());
+        static ref SECP256K1_MONT: MontgomeryParams<4> =
+            MontgomeryParams::new(Secp256K1Mod::modulus());
+    }
+
+    define_prime_field!(Field223 = 223u32);
+    impl MontgomeryMod<1> for Field223 {
+        fn montgomery() -> &'static MontgomeryParams<1> {
+            &FIELD223_MONT
+        }
+    }
+
+    define_prime_field!(Mersenne127 = (BigUint::from(1u32) << 127) - 1u32);
+    impl MontgomeryMod<2> for Mersenne127 {
+        fn montgomery() -> &'static MontgomeryParams<2> {
+            &MERSENNE127_MONT
+        }
+    }
+
+    impl MontgomeryMod<4> for Secp256K1Mod {
+        fn montgomery() -> &'static MontgomeryParams<4> {
+            &SECP256K1_MONT
+        }
+    }
+
+    /// Checks every operation against `FiniteField` on the given values.
+    fn cross_check<F: MontgomeryMod<N> + std::fmt::Debug, const N: usize>(values: &[BigUint]) {
+        for a in values {
+            for b in values {
+                let (fa, fb) = (
+                    FiniteField::<F>::new(a.clone()),
+                    FiniteField::new(b.clone()),
+                );
+                let (ma, mb) = (
+                    MontgomeryField::<F, N>::from(&fa),
+                    MontgomeryField::from(&fb),
+                );
+                assert_eq!(FiniteField::from(ma), fa);
+                assert_eq!(FiniteField::from(ma + mb), &fa + &fb);
+                assert_eq!(FiniteField::from(ma - mb), &fa - &fb);
+                assert_eq!(FiniteField::from(ma * mb), &fa * &fb);
+                assert_eq!(
+                    ma.checked_div(&mb).map(FiniteField::from),
+                    fa.checked_div(&fb)
+                );
+            }
+        }
+    }
+
+    fn edge_values(p: &BigUint) -> Vec<BigUint> {
+        vec![
+            0u32.into(),
+            1u32.into(),
+            2u32.into(),
+            p >> 1,
+            (p >> 1) + 1u32,
+            p - 2u32,
+            p - 1u32,
+        ]
+    }
+
+    #[test]
+    fn test_params() {
+        let params = Field223::montgomery();
+        assert_eq!(params.modulus, [223]);
+        assert_eq!(params.inv.wrapping_mul(223), u64::MAX);
+        let r = BigUint::from(1u32) << 64;
+        assert_eq!(BigUint::from(params.one[0]), &r % 223u32);
+        assert_eq!(BigUint::from(params.r2[0]), (&r * &r) % 223u32);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_params_even_modulus() {
+        let _ = MontgomeryParams::<1>::new(&BigUint::from(224u32));
+    }
+
+    #[test]
+    fn test_cross_check_single_limb() {
+        let values: Vec<BigUint> = (0u32..223).step_by(7).map(BigUint::from).collect();
+        cross_check::<Field223, 1>(&values);
+        cross_check::<Field223, 1>(&edge_values(Field223::modulus()));
+    }
+
+    #[test]
+    fn test_cross_check_multi_limb() {
+        let mut values = edge_values(Mersenne127::modulus());
+        values.push(BigUint::from(u64::MAX));
+        values.push(BigUint::from(u64::MAX) + 1u32);
+        cross_check::<Mersenne127, 2>(&values);
+
+        let mut values = edge_values(Secp256K1Mod::modulus());
+        values.push(
+            FiniteField::<Secp256K1Mod>::from(SECP256K1_GX)
+                .num()
+                .clone(),
+        );
+        values.push(
+            FiniteField::<Secp256K1Mod>::from(SECP256K1_GY)
+                .num()
+                .clone(),
+        );
+        cross_check::<Secp256K1Mod, 4>(&values);
+    }
+
+    #[test]
+    fn test_from_u32() {
+        let a: MontgomeryField<Field223, 1> = 5u32.into();
+        assert_eq!(a.to_biguint(), BigUint::from(5u32));
+        assert_eq!(
+            MontgomeryField::<Field223, 1>::from(1u32),
+            MontgomeryField::one()
+        );
+    }
+
+    #[test]
+    fn test_curve() {
+        type Fe = MontgomeryField<Secp256K1Mod, 4>;
+        let curve: EllipticCurve<Fe> = EllipticCurve::new(0u32, 7u32);
+        let g = curve.point_at(
+            Fe::from(FiniteField::from(SECP256K1_GX)),
+            Fe::from(FiniteField::from(SECP256K1_GY)),
+        );
+        assert_ne!(g.p, PointType::Invalid);
+
+        match ((&g * 1000u32).p, (&*SECP256K1_G * 1000u32).p) {
+            (PointType::Point(a), PointType::Point(b)) => {
+                assert_eq!(FiniteField::from(a.x), FiniteField::from(b.x));
+                assert_eq!(FiniteField::from(a.y), FiniteField::from(b.y));
+            }
+            _ => panic!("Multiples of the generator should be points"),
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Integer algorithms used to validate and analyse moduli.
+use num_bigint::{BigInt, BigUint};
+
+/// The first thirteen primes, used both for trial division and as the Miller–Rabin bases.
+/// With these bases the test is exact for every n below 3.3 * 10^24 (Sorenson and Webster,
+/// 2015).
+const SMALL_PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Deterministic Miller–Rabin primality test.
+///
+/// The bases are fixed, so the answer is always the same for a given `n`. It is proven
+/// correct only for n < 3.3 * 10^24. Above that it is a fixed-base probable-prime test: no
+/// error bound applies, since composites passing all thirteen bases exist, but none is known
+/// among the standard curve parameters.
+pub fn is_prime(n: &BigUint) -> bool {
+    let two = BigUint::from(2u32);
+    if *n < two {
+        return false;
+    }
+    for p in SMALL_PRIMES {
+        if *n == p.into() {
+            return true;
+        }
+        if n % p == 0u32.into() {
+            return false;
+        }
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let one = BigUint::from(1u32);
+    let n_minus_1 = n - 1u32;
+    let s = n_minus_1.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_1 >> s;
+
+    'bases: for a in SMALL_PRIMES {
+        let mut x = BigUint::from(a).modpow(&d, n);
+        if x == one || x == n_minus_1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// The greatest common divisor of `a` and `b`, with gcd(0, 0) = 0.
+pub fn gcd(a: &BigUint, b: &BigUint) -> BigUint {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while b != 0u32.into() {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Inverts `a` modulo any `n > 1` with the extended Euclidean algorithm. Returns `None` if
+/// gcd(a, n) is not 1. Unlike [`crate::inversion`], `n` may be even.
+pub fn mod_inverse(a: &BigUint, n: &BigUint) -> Option<BigUint> {
+    // Invariant: s * a ≡ r (mod n) for both rows.
+    let (mut r0, mut r1) = (BigInt::from(n.clone()), BigInt::from(a % n));
+    let (mut s0, mut s1) = (BigInt::from(0), BigInt::from(1));
+    while r1 != 0.into() {
+        let q = &r0 / &r1;
+        let r2 = &r0 - &q * &r1;
+        let s2 = &s0 - &q * &s1;
+        (r0, r1) = (r1, r2);
+        (s0, s1) = (s1, s2);
+    }
+    if r0 != 1.into() {
+        return None;
+    }
+    let n = BigInt::from(n.clone());
+    (((s0 % &n) + &n) % &n).to_biguint()
+}
+
+/// Factors `n` into primes, returning `(prime, exponent)` pairs in increasing order of prime.
+///
+/// Small factors are removed by trial division and the rest split with Pollard's rho, so this
+/// is fast when all but one prime factor have at most about 20 digits, as for p - 1 of the
+/// small fields used in examples. It may take very long for a product of two large primes.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn factorize(n: &BigUint) -> Vec<(BigUint, u32)> {
+    assert!(*n != 0u32.into(), "cannot factor zero");
+    let mut primes = Vec::new();
+    let mut n = n.clone();
+    let mut d = 2u32;
+    while d < 1000 && n > 1u32.into() {
+        while (&n % d) == 0u32.into() {
+            primes.push(BigUint::from(d));
+            n /= d;
+        }
+        d += 1;
+    }
+
+    let mut composites = vec![n];
+    while let Some(m) = composites.pop() {
+        if m == 1u32.into() {
+            continue;
+        }
+        if is_prime(&m) {
+            primes.push(m);
+            continue;
+        }
+        let divisor = pollard_rho(&m);
+        composites.push(&m / &divisor);
+        composites.push(divisor);
+    }
+
+    primes.sort();
+    let mut factors: Vec<(BigUint, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+/// Finds a non-trivial divisor of the odd composite `n` with Pollard's rho and Floyd's cycle
+/// detection, retrying with a different polynomial x^2 + c when a walk fails.
+fn pollard_rho(n: &BigUint) -> BigUint {
+    let one = BigUint::from(1u32);
+    let mut c = BigUint::from(1u32);
+    loop {
+        let f = |x: &BigUint| (x * x + &c) % n;
+        let (mut x, mut y) = (BigUint::from(2u32), BigUint::from(2u32));
+        let mut d = one.clone();
+        while d == one {
+            x = f(&x);
+            y = f(&f(&y));
+            let diff = match x >= y {
+                true => &x - &y,
+                false => &y - &x,
+            };
+            d = gcd(&diff, n);
+        }
+        if d != *n {
+            return d;
+        }
+        c += 1u32;
+    }
+}
+
+/// Solves the system x ≡ r_i (mod m_i) for pairwise coprime moduli with the Chinese remainder
+/// theorem, returning the unique solution below the product of the moduli.
+///
+/// # Panics
+///
+/// Panics if two moduli are not coprime.
+pub fn crt(congruences: &[(BigUint, BigUint)]) -> BigUint {
+    let product: BigUint = congruences.iter().map(|(_, m)| m).product();
+    let mut x = BigUint::from(0u32);
+    for (r, m) in congruences {
+        let rest = &product / m;
+        let inv = mod_inverse(&rest, m).expect("moduli must be pairwise coprime");
+        x += r * &rest * inv;
+    }
+    x % product
+}
+
+/// Computes `a * b mod m` without overflow.
+pub const fn mul_mod_u64(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// Computes `base^exp mod m` by square and multiply.
+pub const fn pow_mod_u64(base: u64, exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod_u64(result, base, m);
+        }
+        base = mul_mod_u64(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Miller–Rabin for 64-bit integers. The first twelve primes as bases make it exact for
+/// every u64, and being a `const fn` lets moduli be checked at compile time.
+pub const fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < 12 {
+        let p = SMALL_PRIMES[i] as u64;
+        if n == p {
+            return true;
+        }
+        if n.is_multiple_of(p) {
+            return false;
+        }
+        i += 1;
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut i = 0;
+    'bases: while i < 12 {
+        let a = SMALL_PRIMES[i] as u64;
+        i += 1;
+        let mut x = pow_mod_u64(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod_u64(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_numbers() {
+        let primes: Vec<u32> = (0u32..200).filter(|n| is_prime(&(*n).into())).collect();
+        let expected: Vec<u32> = (0u32..200)
+            .filter(|n| *n >= 2 && (2..*n).all(|d| n % d != 0))
+            .collect();
+        assert_eq!(primes, expected);
+    }
+
+    #[test]
+    fn test_pseudoprimes() {
+        // Carmichael numbers and strong pseudoprimes to several small bases.
+        for n in [
+            561u64,
+            41041,
+            2047,
+            1373653,
+            25326001,
+            3215031751,
+            3825123056546413051,
+        ] {
+            assert!(!is_prime(&n.into()), "{} is composite", n);
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let secp_p = BigUint::parse_bytes(
+            b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let secp_n = BigUint::parse_bytes(
+            b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap();
+        let mersenne = (BigUint::from(1u32) << 127) - 1u32;
+        assert!(is_prime(&secp_p));
+        assert!(is_prime(&secp_n));
+        assert!(is_prime(&mersenne));
+
+        assert!(!is_prime(&(&secp_p * &secp_n)));
+        // 2^128 + 1 = 59649589127497217 * 5704689200685129054721
+        assert!(!is_prime(&((BigUint::from(1u32) << 128) + 1u32)));
+    }
+
+    #[test]
+    fn test_gcd_and_inverse() {
+        let big = |n: u32| BigUint::from(n);
+        assert_eq!(gcd(&big(12), &big(18)), big(6));
+        assert_eq!(gcd(&big(0), &big(7)), big(7));
+        assert_eq!(mod_inverse(&big(3), &big(10)), Some(big(7)));
+        assert_eq!(mod_inverse(&big(17), &big(3120)), Some(big(2753)));
+        assert_eq!(mod_inverse(&big(6), &big(9)), None);
+        assert_eq!(mod_inverse(&big(0), &big(9)), None);
+        for n in 2u32..60 {
+            for a in 0..n {
+                let expected = (1..n).find(|x| a * x % n == 1).map(big);
+                assert_eq!(mod_inverse(&big(a), &big(n)), expected, "{} mod {}", a, n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_factorize() {
+        let factors = |n: u64| -> Vec<(u64, u32)> {
+            factorize(&n.into())
+                .into_iter()
+                .map(|(p, e)| (p.try_into().unwrap(), e))
+                .collect()
+        };
+        assert_eq!(factors(1), vec![]);
+        assert_eq!(factors(222), vec![(2, 1), (3, 1), (37, 1)]);
+        assert_eq!(factors(252), vec![(2, 2), (3, 2), (7, 1)]);
+        assert_eq!(factors(1 << 20), vec![(2, 20)]);
+        // Two primes too large for trial division.
+        assert_eq!(
+            factors(1000003 * 1000033 * 1000033),
+            vec![(1000003, 1), (1000033, 2)]
+        );
+        assert_eq!(
+            factors(3825123056546413051),
+            vec![(149491, 1), (747451, 1), (34233211, 1)]
+        );
+    }
+
+    #[test]
+    fn test_crt() {
+        let big = |n: u32| BigUint::from(n);
+        let x = crt(&[(big(2), big(3)), (big(3), big(5)), (big(2), big(7))]);
+        assert_eq!(x, big(23));
+        assert_eq!(crt(&[(big(5), big(11))]), big(5));
+    }
+
+    #[test]
+    fn test_is_prime_u64() {
+        for n in 0u64..3000 {
+            assert_eq!(is_prime_u64(n), is_prime(&n.into()), "{}", n);
+        }
+        for n in [3215031751u64, 3825123056546413051, u64::MAX] {
+            assert!(!is_prime_u64(n));
+        }
+        // The largest 64-bit prime and 2^61 - 1.
+        assert!(is_prime_u64(18446744073709551557));
+        assert!(is_prime_u64((1 << 61) - 1));
+    }
+}
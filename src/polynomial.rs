@@ -0,0 +1,520 @@
+//! Univariate polynomials with coefficients in a prime field.
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num_bigint::BigUint;
+
+use crate::finite_field::{FieldMod, FiniteField};
+
+/// A polynomial over F_p, stored as its coefficients lowest degree first.
+///
+/// The coefficient vector never ends in a zero, so the zero polynomial has no coefficients
+/// and two polynomials are equal exactly when their coefficient vectors are.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Polynomial<F: FieldMod> {
+    coeffs: Vec<FiniteField<F>>,
+}
+
+impl<F: FieldMod> Polynomial<F> {
+    /// Creates a polynomial from its coefficients, lowest degree first. Trailing zeros are
+    /// dropped.
+    pub fn new(coeffs: Vec<FiniteField<F>>) -> Self {
+        let mut coeffs = coeffs;
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(vec![])
+    }
+
+    pub fn one() -> Self {
+        Self::constant(FiniteField::one())
+    }
+
+    pub fn constant(c: FiniteField<F>) -> Self {
+        Self::new(vec![c])
+    }
+
+    /// The polynomial x.
+    pub fn x() -> Self {
+        Self::monomial(FiniteField::one(), 1)
+    }
+
+    /// The polynomial c * x^n.
+    pub fn monomial(c: FiniteField<F>, n: usize) -> Self {
+        let mut coeffs = vec![FiniteField::zero(); n];
+        coeffs.push(c);
+        Self::new(coeffs)
+    }
+
+    /// The coefficients of this polynomial, lowest degree first, without trailing zeros.
+    pub fn coefficients(&self) -> &[FiniteField<F>] {
+        &self.coeffs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The degree of this polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// The coefficient of the highest power of x, or `None` for the zero polynomial.
+    pub fn leading_coefficient(&self) -> Option<&FiniteField<F>> {
+        self.coeffs.last()
+    }
+
+    /// Evaluates this polynomial at `x` with Horner's rule.
+    pub fn evaluate(&self, x: &FiniteField<F>) -> FiniteField<F> {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(FiniteField::zero(), |acc, c| &(&acc * x) + c)
+    }
+
+    /// Multiplies every coefficient by `c`.
+    pub fn scale(&self, c: &FiniteField<F>) -> Self {
+        Self::new(self.coeffs.iter().map(|a| a * c).collect())
+    }
+
+    /// This polynomial divided by its leading coefficient. The zero polynomial is returned as
+    /// is.
+    pub fn monic(&self) -> Self {
+        match self.leading_coefficient() {
+            Some(lead) => self.scale(&lead.inverse().expect("leading coefficient is non-zero")),
+            None => self.clone(),
+        }
+    }
+
+    /// The formal derivative.
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| c * &FiniteField::from_unreduced(i as u64))
+                .collect(),
+        )
+    }
+
+    /// Divides by `divisor`, returning the quotient and the remainder, whose degree is below
+    /// that of `divisor`. Returns `None` if `divisor` is zero.
+    pub fn checked_div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        let lead_inv = divisor.leading_coefficient()?.inverse()?;
+        let d = divisor.coeffs.len();
+        if self.coeffs.len() < d {
+            return Some((Self::zero(), self.clone()));
+        }
+
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![FiniteField::zero(); remainder.len() - d + 1];
+        for top in (d - 1..remainder.len()).rev() {
+            let c = &remainder[top] * &lead_inv;
+            if c.is_zero() {
+                continue;
+            }
+            let shift = top + 1 - d;
+            for (i, b) in divisor.coeffs.iter().enumerate() {
+                remainder[shift + i] -= &c * b;
+            }
+            quotient[shift] = c;
+        }
+        remainder.truncate(d - 1);
+        Some((Self::new(quotient), Self::new(remainder)))
+    }
+
+    /// Divides by `divisor`, returning the quotient and the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        self.checked_div_rem(divisor)
+            .expect("attempt to divide by the zero polynomial")
+    }
+
+    /// The monic greatest common divisor of `self` and `other`, by Euclid's algorithm. The gcd
+    /// of two zero polynomials is zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// Computes `self^exponent mod modulus` by square and multiply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn pow_mod(&self, exponent: &BigUint, modulus: &Self) -> Self {
+        let base = self % modulus;
+        let mut result = &Self::one() % modulus;
+        for i in (0..exponent.bits()).rev() {
+            result = &(&result * &result) % modulus;
+            if exponent.bit(i) {
+                result = &(&result * &base) % modulus;
+            }
+        }
+        result
+    }
+
+    /// Whether this polynomial is irreducible over F_p, by Ben-Or's test: a polynomial f of
+    /// degree k > 0 is irreducible exactly when it has no common factor with x^(p^i) - x for
+    /// any 1 <= i <= k/2.
+    pub fn is_irreducible(&self) -> bool {
+        let k = match self.degree() {
+            None | Some(0) => return false,
+            Some(k) => k,
+        };
+        let x = Self::x();
+        let mut x_p_i = x.clone();
+        for _ in 0..k / 2 {
+            x_p_i = x_p_i.pow_mod(F::modulus(), self);
+            if self.gcd(&(&x_p_i - &x)).degree() != Some(0) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The unique polynomial of degree below n through the n given points, by Lagrange
+    /// interpolation. Returns `None` if two points share an x coordinate.
+    pub fn interpolate(points: &[(FiniteField<F>, FiniteField<F>)]) -> Option<Self> {
+        let mut result = Self::zero();
+        for (i, (xi, yi)) in points.iter().enumerate() {
+            // The basis polynomial is 1 at xi and 0 at every other xj.
+            let mut basis = Self::one();
+            let mut denominator = FiniteField::one();
+            for (j, (xj, _)) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                basis = &basis * &Self::new(vec![-xj, FiniteField::one()]);
+                denominator = &denominator * &(xi - xj);
+            }
+            result = &result + &basis.scale(&(yi * &denominator.inverse()?));
+        }
+        Some(result)
+    }
+
+    /// The distinct roots of this polynomial in F_p, in increasing order.
+    ///
+    /// Every element of the field is tried, so this is only meant for small fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics for the zero polynomial, of which every element is a root.
+    pub fn roots(&self) -> Vec<FiniteField<F>> {
+        assert!(!self.is_zero(), "every element is a root of zero");
+        let mut roots = Vec::new();
+        let mut x = FiniteField::<F>::zero();
+        loop {
+            if self.evaluate(&x).is_zero() {
+                roots.push(x.clone());
+            }
+            x = &x + &FiniteField::one();
+            if x.is_zero() {
+                return roots;
+            }
+        }
+    }
+}
+
+// &T + &T
+impl<F: FieldMod> Add for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let (long, short) = match self.coeffs.len() >= other.coeffs.len() {
+            true => (self, other),
+            false => (other, self),
+        };
+        let mut coeffs = long.coeffs.clone();
+        for (a, b) in coeffs.iter_mut().zip(&short.coeffs) {
+            *a += b;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+// T + T
+impl<F: FieldMod> Add for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn add(self, other: Self) -> Self::Output {
+        &self + &other
+    }
+}
+
+// &T - &T
+impl<F: FieldMod> Sub for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + &-other
+    }
+}
+
+// T - T
+impl<F: FieldMod> Sub for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        &self - &other
+    }
+}
+
+// &T * &T
+impl<F: FieldMod> Mul for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut product = vec![FiniteField::zero(); self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in other.coeffs.iter().enumerate() {
+                product[i + j] += a * b;
+            }
+        }
+        Polynomial::new(product)
+    }
+}
+
+// T * T
+impl<F: FieldMod> Mul for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        &self * &other
+    }
+}
+
+// &T / &T
+impl<F: FieldMod> Div for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.div_rem(other).0
+    }
+}
+
+// T / T
+impl<F: FieldMod> Div for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn div(self, other: Self) -> Self::Output {
+        &self / &other
+    }
+}
+
+// &T % &T
+impl<F: FieldMod> Rem for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn rem(self, other: Self) -> Self::Output {
+        self.div_rem(other).1
+    }
+}
+
+// T % T
+impl<F: FieldMod> Rem for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn rem(self, other: Self) -> Self::Output {
+        &self % &other
+    }
+}
+
+impl<F: FieldMod> Neg for &Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn neg(self) -> Self::Output {
+        Polynomial::new(self.coeffs.iter().map(|c| -c).collect())
+    }
+}
+
+impl<F: FieldMod> Neg for Polynomial<F> {
+    type Output = Polynomial<F>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<F: FieldMod> From<FiniteField<F>> for Polynomial<F> {
+    fn from(value: FiniteField<F>) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<F: FieldMod> Display for Polynomial<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(i, c)| match i {
+                0 => format!("{}", c.num()),
+                1 => format!("{}x", c.num()),
+                _ => format!("{}x^{}", c.num(), i),
+            })
+            .collect();
+        match terms.is_empty() {
+            true => write!(f, "0"),
+            false => write!(f, "{}", terms.join(" + ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+
+    define_prime_field!(Field7 = 7u32);
+    define_prime_field!(Field223 = 223u32);
+
+    type Fe = FiniteField<Field7>;
+    type Poly = Polynomial<Field7>;
+
+    fn poly(coeffs: &[u32]) -> Poly {
+        Poly::new(coeffs.iter().map(|c| Fe::new(*c)).collect())
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(poly(&[1, 2, 0, 0]), poly(&[1, 2]));
+        assert_eq!(poly(&[0, 0]), Poly::zero());
+        assert_eq!(Poly::zero().degree(), None);
+        assert_eq!(poly(&[3]).degree(), Some(0));
+        assert_eq!(Poly::monomial(Fe::new(2u32), 3), poly(&[0, 0, 0, 2]));
+        assert_eq!((&poly(&[1, 2, 3]) - &poly(&[1, 2, 3])).degree(), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = poly(&[1, 2, 3]);
+        let b = poly(&[6, 5]);
+        assert_eq!(&a + &b, poly(&[0, 0, 3]));
+        assert_eq!(&a - &b, poly(&[2, 4, 3]));
+        // (1 + 2x + 3x^2)(6 + 5x) = 6 + 17x + 28x^2 + 15x^3
+        assert_eq!(&a * &b, poly(&[6, 3, 0, 1]));
+        assert_eq!(-&b, poly(&[1, 2]));
+        assert_eq!(&a * &Poly::zero(), Poly::zero());
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let a = poly(&[1, 2, 3, 4, 5]);
+        let b = poly(&[3, 0, 1]);
+        let (q, r) = a.div_rem(&b);
+        assert!(r.degree() < b.degree());
+        assert_eq!(&(&q * &b) + &r, a);
+        assert_eq!(&a / &b, q);
+        assert_eq!(&a % &b, r);
+
+        let (q, r) = b.div_rem(&a);
+        assert_eq!((q, r), (Poly::zero(), b.clone()));
+        assert_eq!(a.checked_div_rem(&Poly::zero()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by the zero polynomial")]
+    fn test_div_by_zero() {
+        let _ = &poly(&[1, 2]) / &Poly::zero();
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let a = poly(&[1, 2, 3]);
+        for x in 0u32..7 {
+            let expected = (1 + 2 * x + 3 * x * x) % 7;
+            assert_eq!(a.evaluate(&Fe::new(x)), Fe::new(expected));
+        }
+        assert_eq!(Poly::zero().evaluate(&Fe::new(3u32)), Fe::zero());
+    }
+
+    #[test]
+    fn test_gcd() {
+        // (x - 1)(x - 2) and (x - 1)(x - 3) share the factor x - 1.
+        let a = poly(&[6, 1]) * poly(&[5, 1]);
+        let b = (poly(&[6, 1]) * poly(&[4, 1])).scale(&Fe::new(3u32));
+        assert_eq!(a.gcd(&b), poly(&[6, 1]));
+        assert_eq!(a.gcd(&Poly::zero()), a.monic());
+        assert_eq!(poly(&[0, 1]).gcd(&poly(&[1, 1])), Poly::one());
+    }
+
+    #[test]
+    fn test_derivative() {
+        assert_eq!(poly(&[5, 1, 2, 3]).derivative(), poly(&[1, 4, 2]));
+        // x^7 has derivative 7x^6 = 0 in characteristic 7.
+        assert_eq!(Poly::monomial(Fe::one(), 7).derivative(), Poly::zero());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let a = poly(&[4, 0, 6, 1]);
+        let points: Vec<(Fe, Fe)> = [1u32, 2, 5, 6]
+            .iter()
+            .map(|x| (Fe::new(*x), a.evaluate(&Fe::new(*x))))
+            .collect();
+        assert_eq!(Poly::interpolate(&points), Some(a));
+        assert_eq!(Poly::interpolate(&[]), Some(Poly::zero()));
+
+        let duplicate = [
+            (Fe::new(1u32), Fe::new(2u32)),
+            (Fe::new(1u32), Fe::new(3u32)),
+        ];
+        assert_eq!(Poly::interpolate(&duplicate), None);
+    }
+
+    #[test]
+    fn test_roots() {
+        // x^3 - x has every element of F_7 that is its own cube as a root: 0, 1 and 6.
+        let a = poly(&[0, 6, 0, 1]);
+        let roots: Vec<Fe> = [0u32, 1, 6].iter().map(|r| Fe::new(*r)).collect();
+        assert_eq!(a.roots(), roots);
+        // x^2 + 1 has no roots since -1 is not a square modulo 7.
+        assert!(poly(&[1, 0, 1]).roots().is_empty());
+
+        // A repeated root is only reported once.
+        let linear =
+            |r: u32| Polynomial::<Field223>::new(vec![-FiniteField::new(r), FiniteField::one()]);
+        let f = linear(5) * linear(100) * linear(100);
+        let expected: Vec<FiniteField<Field223>> = vec![5u32.into(), 100u32.into()];
+        assert_eq!(f.roots(), expected);
+    }
+
+    #[test]
+    fn test_irreducible() {
+        assert!(poly(&[1, 0, 1]).is_irreducible());
+        assert!(poly(&[6, 1]).is_irreducible());
+        assert!(!poly(&[6, 0, 1]).is_irreducible());
+        assert!(!poly(&[3]).is_irreducible());
+        // x^4 + 1 = (x^2 + 3x + 1)(x^2 + 4x + 1) has no roots but still factors.
+        let quartic = poly(&[1, 0, 0, 0, 1]);
+        assert!(quartic.roots().is_empty());
+        assert!(!quartic.is_irreducible());
+        assert_eq!(poly(&[1, 3, 1]) * poly(&[1, 4, 1]), quartic);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(poly(&[3, 0, 2]).to_string(), "2x^2 + 3");
+        assert_eq!(Poly::zero().to_string(), "0");
+    }
+}
@@ -0,0 +1,330 @@
+//! Points in homogeneous projective coordinates with complete formulas.
+//!
+//! The triple (X : Y : Z) stands for the affine point (X / Z, Y / Z), and (0 : 1 : 0) for the
+//! point at infinity. Addition uses Algorithm 1 of Renes, Costello and Batina, "Complete
+//! addition formulas for prime order elliptic curves" (2016), and doubling is the same formula
+//! with both inputs equal. The formulas are correct for every pair of inputs on a curve with
+//! no point of order two, which holds for every curve of odd order such as secp256k1, so they
+//! never branch on the points.
+//!
+//! They cost more multiplications than the branching formulas of
+//! [`JacobianPoint`](crate::jacobian::JacobianPoint), but running the same operations for every
+//! input is what the constant-time [`ladder`](crate::ladder) needs.
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul};
+
+use num_bigint::BigUint;
+
+use crate::algebra::Field;
+use crate::curve::{CurveParams, Point};
+use crate::ec::{Coordinates, PointType};
+use crate::ladder::ConditionalSelect;
+
+/// The arithmetic behind [`ProjectivePoint`], for a curve given by a and 3b.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Projective<T> {
+    pub(crate) x: T,
+    pub(crate) y: T,
+    pub(crate) z: T,
+}
+
+impl<T: Field> Projective<T> {
+    pub(crate) fn infinity() -> Self {
+        Self {
+            x: T::zero(),
+            y: T::one(),
+            z: T::zero(),
+        }
+    }
+
+    pub(crate) fn from_affine(p: &Coordinates<T>) -> Self {
+        Self {
+            x: p.x.clone(),
+            y: p.y.clone(),
+            z: T::one(),
+        }
+    }
+
+    /// Converts an affine point, mapping both infinity and invalid points to infinity.
+    pub(crate) fn from_point_type(p: &PointType<T>) -> Self {
+        match p {
+            PointType::Point(c) => Self::from_affine(c),
+            _ => Self::infinity(),
+        }
+    }
+
+    pub(crate) fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub(crate) fn to_affine(&self) -> PointType<T> {
+        match self.z.inverse() {
+            None => PointType::Infinity,
+            Some(z_inv) => PointType::Point(Coordinates {
+                x: self.x.clone() * z_inv.clone(),
+                y: self.y.clone() * z_inv,
+            }),
+        }
+    }
+
+    /// Complete addition on y^2 = x^3 + ax + b, where `b3` is 3b.
+    pub(crate) fn add(&self, other: &Self, a: &T, b3: &T) -> Self {
+        let (x1, y1, z1) = (self.x.clone(), self.y.clone(), self.z.clone());
+        let (x2, y2, z2) = (other.x.clone(), other.y.clone(), other.z.clone());
+
+        let t0 = x1.clone() * x2.clone();
+        let t1 = y1.clone() * y2.clone();
+        let t2 = z1.clone() * z2.clone();
+        let t3 = (x1.clone() + y1.clone()) * (x2.clone() + y2.clone()) - (t0.clone() + t1.clone());
+        let t4 = (x1 + z1.clone()) * (x2 + z2.clone()) - (t0.clone() + t2.clone());
+        let t5 = (y1 + z1) * (y2 + z2) - (t1.clone() + t2.clone());
+
+        let z3 = a.clone() * t4.clone() + b3.clone() * t2.clone();
+        let x3 = t1.clone() - z3.clone();
+        let z3 = t1 + z3;
+        let y3 = x3.clone() * z3.clone();
+
+        let t1 = t0.clone() + t0.clone() + t0.clone() + a.clone() * t2.clone();
+        let t4 = b3.clone() * t4 + a.clone() * (t0 - a.clone() * t2);
+        let y3 = y3 + t1.clone() * t4.clone();
+        let x3 = t3.clone() * x3 - t5.clone() * t4;
+        let z3 = t5 * z3 + t3 * t1;
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    pub(crate) fn double(&self, a: &T, b3: &T) -> Self {
+        self.add(self, a, b3)
+    }
+
+    /// Whether both triples stand for the same point: X1 Z2 = X2 Z1 and Y1 Z2 = Y2 Z1.
+    pub(crate) fn equals(&self, other: &Self) -> bool {
+        self.x.clone() * other.z.clone() == other.x.clone() * self.z.clone()
+            && self.y.clone() * other.z.clone() == other.y.clone() * self.z.clone()
+    }
+}
+
+impl<T: ConditionalSelect> ConditionalSelect for Projective<T> {
+    fn conditional_select(a: &Self, b: &Self, choice: u64) -> Self {
+        Self {
+            x: T::conditional_select(&a.x, &b.x, choice),
+            y: T::conditional_select(&a.y, &b.y, choice),
+            z: T::conditional_select(&a.z, &b.z, choice),
+        }
+    }
+}
+
+/// 3b for the curve `C`, the form of b the complete formulas use.
+pub(crate) fn b3<C: CurveParams>() -> C::Field {
+    let b = C::b();
+    b.clone() + b.clone() + b
+}
+
+/// A point on the curve `C` in homogeneous projective coordinates.
+pub struct ProjectivePoint<C: CurveParams> {
+    inner: Projective<C::Field>,
+    _curve: PhantomData<fn() -> C>,
+}
+
+impl<C: CurveParams> ProjectivePoint<C> {
+    fn wrap(inner: Projective<C::Field>) -> Self {
+        Self {
+            inner,
+            _curve: PhantomData,
+        }
+    }
+
+    pub fn infinity() -> Self {
+        Self::wrap(Projective::infinity())
+    }
+
+    pub fn generator() -> Self {
+        Self::wrap(Projective::from_affine(&C::generator()))
+    }
+
+    /// Converts an affine point, returning `None` if it is invalid.
+    pub fn from_affine(p: &Point<C>) -> Option<Self> {
+        match p.is_valid() {
+            false => None,
+            true => Some(Self::wrap(Projective::from_point_type(&p.p))),
+        }
+    }
+
+    /// Converts to affine coordinates, which costs one field inversion.
+    pub fn to_affine(&self) -> Point<C> {
+        Point::from_type(self.inner.to_affine())
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.inner.is_infinity()
+    }
+
+    pub fn double(&self) -> Self {
+        Self::wrap(self.inner.double(&C::a(), &b3::<C>()))
+    }
+}
+
+// Implemented by hand so that `C` itself needs none of these traits.
+impl<C: CurveParams> Clone for ProjectivePoint<C> {
+    fn clone(&self) -> Self {
+        Self::wrap(self.inner.clone())
+    }
+}
+
+impl<C: CurveParams> Copy for ProjectivePoint<C> where C::Field: Copy {}
+
+/// Two points are equal if they stand for the same affine point, whatever their Z.
+impl<C: CurveParams> PartialEq for ProjectivePoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+}
+
+impl<C: CurveParams> Eq for ProjectivePoint<C> where C::Field: Eq {}
+
+impl<C: CurveParams> Debug for ProjectivePoint<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectivePoint")
+            .field("x", &self.inner.x)
+            .field("y", &self.inner.y)
+            .field("z", &self.inner.z)
+            .finish()
+    }
+}
+
+impl<C: CurveParams> From<ProjectivePoint<C>> for Point<C> {
+    fn from(p: ProjectivePoint<C>) -> Self {
+        p.to_affine()
+    }
+}
+
+impl<C: CurveParams> Add for &ProjectivePoint<C> {
+    type Output = ProjectivePoint<C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        ProjectivePoint::wrap(self.inner.add(&rhs.inner, &C::a(), &b3::<C>()))
+    }
+}
+
+// T + T
+impl<C: CurveParams> Add for ProjectivePoint<C> {
+    type Output = ProjectivePoint<C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+// T + &T
+impl<C: CurveParams> Add<&ProjectivePoint<C>> for ProjectivePoint<C> {
+    type Output = ProjectivePoint<C>;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        &self + rhs
+    }
+}
+
+// &T + T
+impl<C: CurveParams> Add<ProjectivePoint<C>> for &ProjectivePoint<C> {
+    type Output = ProjectivePoint<C>;
+
+    fn add(self, rhs: ProjectivePoint<C>) -> Self::Output {
+        self + &rhs
+    }
+}
+
+// Base implementation: &T * U
+// The point operations do not branch, but the loop still follows the bits of the scalar.
+// Secret scalars go through `Point::mul_secret` instead.
+impl<C: CurveParams, U: Into<BigUint>> Mul<U> for &ProjectivePoint<C> {
+    type Output = ProjectivePoint<C>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        let n: BigUint = rhs.into();
+        let (a, b3) = (C::a(), b3::<C>());
+        let mut result = Projective::infinity();
+        for i in (0..n.bits()).rev() {
+            result = result.double(&a, &b3);
+            if n.bit(i) {
+                result = result.add(&self.inner, &a, &b3);
+            }
+        }
+        ProjectivePoint::wrap(result)
+    }
+}
+
+// T * U
+impl<C: CurveParams, U: Into<BigUint>> Mul<U> for ProjectivePoint<C> {
+    type Output = ProjectivePoint<C>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        &self * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::test_curves::{Field97, Toy97};
+
+    type ToyPoint = Point<Toy97>;
+    type ToyProjective = ProjectivePoint<Toy97>;
+
+    fn multiples() -> Vec<ToyPoint> {
+        let g = ToyPoint::generator();
+        (0u32..101).map(|k| &g * k).collect()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for p in multiples() {
+            assert_eq!(ToyProjective::from_affine(&p).unwrap().to_affine(), p);
+        }
+        assert_eq!(ToyProjective::from_affine(&ToyPoint::new(1u32, 1u32)), None);
+    }
+
+    #[test]
+    fn test_complete_addition() {
+        // Every pair, including equal points, opposite points and infinity, goes through the
+        // same formula.
+        let points = multiples();
+        let projective: Vec<_> = points
+            .iter()
+            .map(|p| ToyProjective::from_affine(p).unwrap())
+            .collect();
+        for (p, pp) in points.iter().zip(&projective) {
+            assert_eq!(pp.double().to_affine(), p + p);
+            for (q, pq) in points.iter().zip(&projective) {
+                assert_eq!((pp + pq).to_affine(), p + q);
+            }
+        }
+    }
+
+    #[test]
+    fn test_equality() {
+        let g = ToyProjective::generator();
+        let lambda = Field97::new(5u32);
+        let scaled = ToyProjective::wrap(Projective {
+            x: g.inner.x.clone() * lambda.clone(),
+            y: g.inner.y.clone() * lambda.clone(),
+            z: lambda,
+        });
+        assert_eq!(scaled, g);
+        assert_ne!(scaled, g.double());
+        assert_ne!(scaled, ToyProjective::infinity());
+        assert_eq!(ToyProjective::infinity(), g * Toy97::order());
+    }
+
+    #[test]
+    fn test_mul() {
+        let g = ToyProjective::generator();
+        let points = multiples();
+        for k in 0u32..101 {
+            assert_eq!((&g * k).to_affine(), points[k as usize]);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Sources of randomness and uniform sampling of integers below a bound.
+use std::fs::File;
+use std::io::Read;
+
+use num_bigint::BigUint;
+
+use crate::error::{Error, Result};
+
+/// A source of random bytes.
+pub trait Rng {
+    fn fill_bytes(&mut self, dest: &mut [u8]);
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// Operating system entropy read from `/dev/urandom`. This is the default source for keys
+/// and nonces.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsRng;
+
+impl OsRng {
+    /// Fills `dest` from `/dev/urandom`, failing if the device cannot be read.
+    pub fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<()> {
+        File::open("/dev/urandom")
+            .and_then(|mut f| f.read_exact(dest))
+            .map_err(|e| Error::Entropy(e.to_string()))
+    }
+}
+
+impl Rng for OsRng {
+    /// # Panics
+    ///
+    /// Panics if `/dev/urandom` cannot be read. Use [`OsRng::try_fill_bytes`] to handle the
+    /// error instead.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("{}", e);
+        }
+    }
+}
+
+/// A deterministic xoshiro256** generator for tests and reproducible experiments.
+///
+/// It is not cryptographically secure and must never be used for keys or nonces.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: [u64; 4],
+}
+
+impl SeededRng {
+    /// Creates a generator whose state is expanded from `seed` with SplitMix64.
+    pub fn new(seed: u64) -> Self {
+        let mut x = seed;
+        let mut state = [0u64; 4];
+        for s in state.iter_mut() {
+            x = x.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            *s = z ^ (z >> 31);
+        }
+        Self { state }
+    }
+}
+
+impl Rng for SeededRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+}
+
+/// Samples an integer uniformly from `0..bound` by rejection sampling: draw as many bits as
+/// `bound` has and retry when the value is too large. Each draw succeeds with probability
+/// above 1/2.
+///
+/// # Panics
+///
+/// Panics if `bound` is zero.
+pub fn random_below(rng: &mut impl Rng, bound: &BigUint) -> BigUint {
+    assert!(*bound > 0u32.into(), "cannot sample below zero");
+    let bits = bound.bits();
+    let mut bytes = vec![0u8; bits.div_ceil(8) as usize];
+    let excess = bytes.len() as u64 * 8 - bits;
+    loop {
+        rng.fill_bytes(&mut bytes);
+        bytes[0] &= 0xFF >> excess;
+        let candidate = BigUint::from_bytes_be(&bytes);
+        if candidate < *bound {
+            return candidate;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_rng_vector() {
+        let mut rng = SeededRng::new(42);
+        assert_eq!(rng.next_u64(), 0x15780b2e0c2ec716);
+        assert_eq!(rng.next_u64(), 0x6104d9866d113a7e);
+        assert_eq!(rng.next_u64(), 0xae17533239e499a1);
+    }
+
+    #[test]
+    fn test_seeded_rng_fill_bytes() {
+        let mut a = SeededRng::new(7);
+        let mut b = SeededRng::new(7);
+        let mut bytes = [0u8; 12];
+        a.fill_bytes(&mut bytes);
+        assert_eq!(bytes[..8], b.next_u64().to_le_bytes());
+        assert_eq!(bytes[8..], b.next_u64().to_le_bytes()[..4]);
+
+        assert_ne!(SeededRng::new(1).next_u64(), SeededRng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_os_rng() {
+        let mut rng = OsRng;
+        let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+        rng.fill_bytes(&mut a);
+        rng.fill_bytes(&mut b);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_random_below_uniform() {
+        let mut rng = SeededRng::new(1);
+        let bound = BigUint::from(7u32);
+        let mut counts = [0u32; 7];
+        for _ in 0..7000 {
+            let n: usize = random_below(&mut rng, &bound).try_into().unwrap();
+            counts[n] += 1;
+        }
+        for count in counts {
+            assert!((800..1200).contains(&count), "counts {:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_random_below_large_bound() {
+        let mut rng = SeededRng::new(3);
+        let bound = (BigUint::from(1u32) << 255) + 19u32;
+        for _ in 0..100 {
+            assert!(random_below(&mut rng, &bound) < bound);
+        }
+        assert_eq!(random_below(&mut rng, &1u32.into()), 0u32.into());
+    }
+}
@@ -0,0 +1,428 @@
+//! Scalar recodings and precomputed tables for faster scalar multiplication.
+//!
+//! Variable-base multiplication recodes the scalar in width-w NAF, so only one in about w + 1
+//! digits needs an addition. Multiples of a fixed base, such as the generator in key generation
+//! and signing, come from a [`FixedBaseTable`] and need no doublings at all. Sums of many
+//! multiples, as in signature verification, share their doublings in [`multi_scalar_mul`].
+//!
+//! [`FixedBaseTable::mul`] runs in constant time. Everything else here runs in variable time;
+//! see [`ladder`](crate::ladder) for secret scalars and arbitrary points.
+use num_bigint::BigUint;
+
+use crate::algebra::Field;
+use crate::curve::{CurveParams, Point};
+use crate::ec::{Coordinates, PointType};
+use crate::jacobian::{batch_to_affine, Jacobian};
+use crate::ladder::ConditionalSelect;
+use crate::projective::{b3, Projective};
+
+/// Recodes `n` in width-`w` non-adjacent form, least significant digit first.
+///
+/// Every non-zero digit is odd and below 2^(w-1) in absolute value, and any w consecutive
+/// digits hold at most one non-zero digit. `w` must be between 2 and 8.
+pub fn wnaf(n: &BigUint, w: u32) -> Vec<i8> {
+    assert!((2..=8).contains(&w), "window width must be between 2 and 8");
+    let modulus = 1i16 << w;
+    let mut k = n.clone();
+    let mut digits = Vec::with_capacity(n.bits() as usize + 1);
+    while k.bits() > 0 {
+        let digit = match k.bit(0) {
+            false => 0,
+            true => {
+                let low = (k.iter_u64_digits().next().unwrap_or(0) & (modulus as u64 - 1)) as i16;
+                // Pick the residue in (-2^(w-1), 2^(w-1)) so the next w - 1 digits are zero.
+                let digit = match low >= modulus / 2 {
+                    true => low - modulus,
+                    false => low,
+                };
+                match digit < 0 {
+                    true => k += digit.unsigned_abs(),
+                    false => k -= digit as u16,
+                }
+                digit as i8
+            }
+        };
+        digits.push(digit);
+        k >>= 1;
+    }
+    digits
+}
+
+/// The NAF width of [`straus`], which stores 2^(w-2) odd multiples of every point.
+const STRAUS_WIDTH: u32 = 5;
+
+/// Above this many terms [`multi_scalar_mul`] switches from Straus to Pippenger. On secp256k1
+/// Straus is faster at 192 terms (3.7 to 4.5 ms against 4.5 to 4.7 ms), the two are level at
+/// 256 (4.7 to 5.8 ms against 5.3 ms), and Pippenger is faster at 384 (7.0 to 8.2 ms against
+/// 8.7 ms). These are the `secp256k1_multi_scalar_mul_*` benches in `bench_sekp`, run with
+/// this constant set to `usize::MAX` and then to 0; the ranges span two runs.
+const STRAUS_MAX_TERMS: usize = 256;
+
+/// Computes the sum of `scalar * point` over `terms`.
+///
+/// All terms share one chain of doublings. Up to 256 terms this interleaves their width-5 NAFs
+/// (Straus), and for more it sorts the points into buckets per window of the scalars
+/// (Pippenger), whose cost per term shrinks as the number of terms grows. Returns an invalid
+/// point if any of the points is invalid.
+pub fn multi_scalar_mul<C: CurveParams>(terms: &[(BigUint, Point<C>)]) -> Point<C> {
+    let mut points = Vec::with_capacity(terms.len());
+    for (scalar, point) in terms {
+        match &point.p {
+            PointType::Invalid => return Point::from_type(PointType::Invalid),
+            PointType::Infinity => {}
+            PointType::Point(c) => points.push((scalar, c)),
+        }
+    }
+    let a = C::a();
+    let sum = match points.len() <= STRAUS_MAX_TERMS {
+        true => straus(&points, &a),
+        false => pippenger(&points, &a),
+    };
+    Point::from_type(sum.to_affine())
+}
+
+/// Interleaved width-w NAF multiplication: the odd multiples of every point are precomputed
+/// and normalized with a single inversion, then one pass over the digits adds them in.
+pub(crate) fn straus<T: Field>(terms: &[(&BigUint, &Coordinates<T>)], a: &T) -> Jacobian<T> {
+    let per_point = 1 << (STRAUS_WIDTH - 2);
+    let digits: Vec<Vec<i8>> = terms.iter().map(|(k, _)| wnaf(k, STRAUS_WIDTH)).collect();
+
+    // P, 3P, ..., 15P for every point, in one table.
+    let mut odd = Vec::with_capacity(terms.len() * per_point);
+    for (_, p) in terms {
+        let base = Jacobian::from_affine(p);
+        let twice_p = base.double(a);
+        odd.push(base);
+        for _ in 1..per_point {
+            let next = odd.last().expect("non-empty").add(&twice_p, a);
+            odd.push(next);
+        }
+    }
+    let table = batch_to_affine(&odd);
+
+    let len = digits.iter().map(Vec::len).max().unwrap_or(0);
+    let mut result = Jacobian::infinity();
+    for i in (0..len).rev() {
+        result = result.double(a);
+        for (j, point_digits) in digits.iter().enumerate() {
+            match point_digits.get(i) {
+                Some(&digit) if digit != 0 => {
+                    let entry = &table[j * per_point + (digit.unsigned_abs() / 2) as usize];
+                    result = result.add_signed(entry, digit < 0, a);
+                }
+                _ => {}
+            }
+        }
+    }
+    result
+}
+
+/// Bucket method: for every c-bit window of the scalars, each point is added to the bucket of
+/// its digit, and the buckets are combined into the sum of digit times bucket with running sums.
+fn pippenger<T: Field>(terms: &[(&BigUint, &Coordinates<T>)], a: &T) -> Jacobian<T> {
+    let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+    let limbs: Vec<Vec<u64>> = terms.iter().map(|(k, _)| k.to_u64_digits()).collect();
+
+    // Each window costs one addition per term and two per bucket.
+    let n = terms.len() as u64;
+    let c = (2..=16u64)
+        .min_by_key(|c| bits.div_ceil(*c) * (n + (2 << c)))
+        .expect("non-empty range");
+
+    let mut result = Jacobian::infinity();
+    for w in (0..bits.div_ceil(c)).rev() {
+        for _ in 0..c {
+            result = result.double(a);
+        }
+        let mut buckets = vec![Jacobian::infinity(); (1 << c) - 1];
+        for (k, (_, p)) in limbs.iter().zip(terms) {
+            let digit = window(k, w * c, c);
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].add_affine(p, a);
+            }
+        }
+        // running = the sum of buckets d and above, so the sum of the running values counts
+        // bucket d exactly d times.
+        let mut running = Jacobian::infinity();
+        let mut sum = Jacobian::infinity();
+        for bucket in buckets.iter().rev() {
+            running = running.add(bucket, a);
+            sum = sum.add(&running, a);
+        }
+        result = result.add(&sum, a);
+    }
+    result
+}
+
+/// The `width` bits of a little-endian number starting at bit `start`.
+fn window(limbs: &[u64], start: u64, width: u64) -> usize {
+    let (limb, shift) = ((start / 64) as usize, start % 64);
+    let mut bits = limbs.get(limb).map_or(0, |l| l >> shift);
+    if shift + width > 64 {
+        bits |= limbs.get(limb + 1).map_or(0, |l| l << (64 - shift));
+    }
+    (bits & ((1 << width) - 1)) as usize
+}
+
+/// The bits per window of a [`FixedBaseTable`].
+const WINDOW: u64 = 4;
+
+/// Precomputed multiples of a fixed point for multiplication without doublings.
+///
+/// The scalar is split into 4-bit windows, and the table holds d * 16^i * P for every window i
+/// and digit d, normalized so that Z = 1. For a 256-bit order that is 960 points, built once
+/// with a single inversion.
+///
+/// [`FixedBaseTable::mul`] runs in constant time, so it suits secret scalars: it goes through
+/// every window of the order, reads all 15 entries of each with [`ConditionalSelect`], and adds
+/// with the complete formulas of [`projective`](crate::projective).
+pub struct FixedBaseTable<C: CurveParams> {
+    /// Window i occupies entries 15 i to 15 i + 14, for the digits 1 to 15.
+    table: Vec<Projective<C::Field>>,
+    order: BigUint,
+}
+
+impl<C: CurveParams> FixedBaseTable<C> {
+    /// Builds the table for `base`, which must lie in the subgroup generated by the generator of
+    /// `C`, since scalars are reduced modulo its order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is invalid.
+    pub fn new(base: &Point<C>) -> Self {
+        let order = C::order();
+        let windows = order.bits().div_ceil(WINDOW);
+        let a = C::a();
+
+        let mut window_base = match &base.p {
+            PointType::Invalid => panic!("cannot precompute multiples of an invalid point"),
+            PointType::Infinity => Jacobian::infinity(),
+            PointType::Point(c) => Jacobian::from_affine(c),
+        };
+        let mut points = Vec::with_capacity((windows * 15) as usize);
+        for _ in 0..windows {
+            let mut multiple = window_base.clone();
+            for _ in 0..15 {
+                points.push(multiple.clone());
+                multiple = multiple.add(&window_base, &a);
+            }
+            // 16 times the window base, the first multiple not stored.
+            window_base = multiple;
+        }
+
+        Self {
+            table: batch_to_affine(&points)
+                .iter()
+                .map(Projective::from_point_type)
+                .collect(),
+            order,
+        }
+    }
+
+    /// Builds the table for the generator of `C`.
+    pub fn generator() -> Self {
+        Self::new(&Point::generator())
+    }
+}
+
+impl<C: CurveParams> FixedBaseTable<C>
+where
+    C::Field: ConditionalSelect,
+{
+    /// Multiplies the base point by `n` in constant time.
+    ///
+    /// As in [`Point::mul_secret`], `n` is first reduced modulo the order, which is only
+    /// constant time for scalars already below it.
+    pub fn mul(&self, n: impl Into<BigUint>) -> Point<C> {
+        let n = n.into() % &self.order;
+        let (a, b3) = (C::a(), b3::<C>());
+        let windows = self.order.bits().div_ceil(WINDOW);
+        let limbs = n.to_u64_digits();
+
+        let mut result = Projective::infinity();
+        for i in 0..windows {
+            let digit = window(&limbs, i * WINDOW, WINDOW) as u64;
+            let entries = &self.table[(i * 15) as usize..(i * 15 + 15) as usize];
+            // Digit 0 keeps the point at infinity, which the complete addition handles.
+            let mut entry = Projective::infinity();
+            for (d, candidate) in (1u64..).zip(entries) {
+                entry = Projective::conditional_select(&entry, candidate, ct_eq(digit, d));
+            }
+            result = result.add(&entry, &a, &b3);
+        }
+        Point::from_type(result.to_affine())
+    }
+}
+
+/// 1 if `a == b` and 0 otherwise, without branching.
+fn ct_eq(a: u64, b: u64) -> u64 {
+    let diff = a ^ b;
+    1 ^ ((diff | diff.wrapping_neg()) >> 63)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::test_curves::Toy97;
+    use crate::rng::{random_below, SeededRng};
+    use crate::secp256k1::{random_scalar, Secp256K1, Secp256K1Point, SECP256K1_N};
+    use num_bigint::BigInt;
+
+    fn from_digits(digits: &[i8]) -> BigInt {
+        digits
+            .iter()
+            .rev()
+            .fold(BigInt::from(0), |acc, &d| acc * 2 + d)
+    }
+
+    #[test]
+    fn test_wnaf() {
+        let mut rng = SeededRng::new(22);
+        let mut samples: Vec<BigUint> = (0u32..300).map(BigUint::from).collect();
+        samples.extend((0..20).map(|_| random_scalar(&mut rng)));
+        samples.push(SECP256K1_N.clone());
+
+        for w in 2..=8 {
+            for n in &samples {
+                let digits = wnaf(n, w);
+                assert_eq!(from_digits(&digits), BigInt::from(n.clone()));
+                assert!(digits.len() as u64 <= n.bits() + 1);
+                assert_ne!(digits.last(), Some(&0));
+                for (i, &d) in digits.iter().enumerate() {
+                    if d != 0 {
+                        assert_eq!(d % 2, d.signum());
+                        assert!((d.unsigned_abs() as u32) < 1 << (w - 1));
+                        let next = &digits[i + 1..digits.len().min(i + w as usize)];
+                        assert!(next.iter().all(|&d| d == 0));
+                    }
+                }
+            }
+        }
+        assert!(wnaf(&BigUint::from(0u32), 4).is_empty());
+    }
+
+    #[test]
+    fn test_window() {
+        let limbs = [0xF000_0000_0000_0001, 0xAB];
+        assert_eq!(window(&limbs, 0, 4), 1);
+        assert_eq!(window(&limbs, 60, 8), 0xBF);
+        assert_eq!(window(&limbs, 64, 16), 0xAB);
+        assert_eq!(window(&limbs, 128, 4), 0);
+    }
+
+    fn naive_sum(terms: &[(BigUint, Point<Toy97>)]) -> Point<Toy97> {
+        terms
+            .iter()
+            .fold(Point::infinity(), |acc, (k, p)| acc + p * k.clone())
+    }
+
+    fn toy_terms(n: usize, rng: &mut SeededRng) -> Vec<(BigUint, Point<Toy97>)> {
+        let g = Point::<Toy97>::generator();
+        (0..n)
+            .map(|i| {
+                // Include zero scalars, scalars above the order and repeated points.
+                let k = random_below(rng, &BigUint::from(300u32));
+                (k, &g * (i as u32 % 7 + 1))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        let mut rng = SeededRng::new(24);
+        for n in [0, 1, 2, 3, 10, 250, 257] {
+            let terms = toy_terms(n, &mut rng);
+            assert_eq!(multi_scalar_mul(&terms), naive_sum(&terms));
+        }
+    }
+
+    #[test]
+    fn test_straus_and_pippenger() {
+        let mut rng = SeededRng::new(25);
+        let a = Toy97::a();
+        for n in [1, 2, 5, 30] {
+            let terms = toy_terms(n, &mut rng);
+            let points: Vec<_> = terms
+                .iter()
+                .map(|(k, p)| match &p.p {
+                    PointType::Point(c) => (k, c),
+                    _ => unreachable!("multiples of the generator below its order"),
+                })
+                .collect();
+            let expected = naive_sum(&terms);
+            let straus = Point::<Toy97>::from_type(straus(&points, &a).to_affine());
+            let pippenger = Point::<Toy97>::from_type(pippenger(&points, &a).to_affine());
+            assert_eq!(straus, expected);
+            assert_eq!(pippenger, expected);
+        }
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_special_points() {
+        let g = Point::<Toy97>::generator();
+        let minus_g = &g * 100u32;
+        let terms = vec![
+            (BigUint::from(5u32), g.clone()),
+            (BigUint::from(5u32), minus_g),
+            (BigUint::from(9u32), Point::infinity()),
+        ];
+        assert_eq!(multi_scalar_mul(&terms), Point::infinity());
+
+        let invalid = Point::<Toy97>::new(1u32, 1u32);
+        let terms = vec![(BigUint::from(1u32), g), (BigUint::from(1u32), invalid)];
+        assert!(!multi_scalar_mul(&terms).is_valid());
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_secp256k1() {
+        let mut rng = SeededRng::new(26);
+        let g = Secp256K1Point::generator();
+        let p = g * 0xDEADBEEFu32;
+        let (u1, u2) = (random_scalar(&mut rng), random_scalar(&mut rng));
+        let terms = vec![(u1.clone(), g), (u2.clone(), p)];
+        assert_eq!(multi_scalar_mul(&terms), g * u1 + p * u2);
+    }
+
+    #[test]
+    #[should_panic(expected = "window width must be between 2 and 8")]
+    fn test_wnaf_width() {
+        wnaf(&BigUint::from(5u32), 9);
+    }
+
+    #[test]
+    fn test_fixed_base() {
+        let table = FixedBaseTable::<Secp256K1>::generator();
+        let g = Secp256K1Point::generator();
+        let mut rng = SeededRng::new(23);
+        let mut scalars: Vec<BigUint> = (0u32..20).map(BigUint::from).collect();
+        scalars.extend((0..8).map(|_| random_scalar(&mut rng)));
+        scalars.push(&*SECP256K1_N - 1u32);
+        for k in scalars {
+            assert_eq!(table.mul(k.clone()), g * k);
+        }
+        assert_eq!(table.mul(SECP256K1_N.clone()), Secp256K1Point::infinity());
+        assert_eq!(table.mul(&*SECP256K1_N + 5u32), g * 5u32);
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        for (a, b) in [
+            (0, 0),
+            (5, 5),
+            (0, 1),
+            (1, 0),
+            (15, 7),
+            (u64::MAX, 0),
+            (0, 1 << 63),
+        ] {
+            assert_eq!(ct_eq(a, b), (a == b) as u64);
+        }
+    }
+
+    #[test]
+    fn test_fixed_base_other_point() {
+        let p = Secp256K1Point::generator() * 7u32;
+        let table = FixedBaseTable::new(&p);
+        assert_eq!(table.mul(3u32), Secp256K1Point::generator() * 21u32);
+    }
+}
@@ -1,15 +1,27 @@
 use crate::{
-    ec::{ECurvePoint, EllipticCurve},
-    finite_field::{FieldMod, FiniteField},
+    curve::{CurveParams, Point},
+    define_prime_field,
+    ec::{Coordinates, ECurvePoint, EllipticCurve},
+    rng::{random_below, Rng},
+    scalar_mul::FixedBaseTable,
+    secp256k1_field::Secp256K1Field,
 };
 use lazy_static::lazy_static;
 use num_bigint::BigUint;
 
-type _SECPField = FiniteField<Secp256K1Mod>;
+type _SECPField = Secp256K1Field;
 
 static SECP256K1_A: u32 = 0u32;
 static SECP256K1_B: u32 = 7u32;
 
+/// The x coordinate of the generator on the secp256k1 curve.
+pub const SECP256K1_GX: _SECPField =
+    _SECPField::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+
+/// The y coordinate of the generator on the secp256k1 curve.
+pub const SECP256K1_GY: _SECPField =
+    _SECPField::from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+
 lazy_static! {
     /// The prime used on the secp256k1 curve.
     pub static ref SECP256K1_PRIME: BigUint = BigUint::parse_bytes(
@@ -18,26 +30,8 @@ lazy_static! {
     )
     .unwrap();
 
-    /// The x coordinate of the generator on the secp256k1 curve.
-    pub static ref SECP256K1_GX: _SECPField = _SECPField::new(
-        BigUint::parse_bytes(
-            b"79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
-            16
-        )
-        .unwrap()
-    );
-
-    /// The y coordinate of the generator on the secp256k1 curve.
-    pub static ref SECP256K1_GY: _SECPField = _SECPField::new(
-        BigUint::parse_bytes(
-            b"483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
-            16
-        )
-        .unwrap()
-    );
-
     /// The generator point on the secp256k1 curve.
-    pub static ref SECP256K1_G: ECurvePoint<'static, FiniteField<Secp256K1Mod>> = SECP256K1.point_at(SECP256K1_GX.clone(), SECP256K1_GY.clone());
+    pub static ref SECP256K1_G: ECurvePoint<'static, _SECPField> = SECP256K1.point_at(SECP256K1_GX, SECP256K1_GY);
 
     /// The order of the generator on the secp256k1 curve.
     pub static ref SECP256K1_N: BigUint = BigUint::parse_bytes(
@@ -47,39 +41,195 @@ lazy_static! {
     .unwrap();
 
     /// The secp256k1 curve.
-    pub static ref SECP256K1: EllipticCurve<FiniteField<Secp256K1Mod>> =
+    pub static ref SECP256K1: EllipticCurve<_SECPField> =
         EllipticCurve::new(SECP256K1_A, SECP256K1_B);
+
+    /// Precomputed multiples of the generator, used by [`mul_generator`].
+    static ref SECP256K1_G_TABLE: FixedBaseTable<Secp256K1> = FixedBaseTable::generator();
 }
 
+/// The secp256k1 curve as a type, for [`Point`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Secp256K1Mod;
+pub struct Secp256K1;
+
+impl CurveParams for Secp256K1 {
+    type Field = _SECPField;
+
+    fn a() -> _SECPField {
+        SECP256K1_A.into()
+    }
+
+    fn b() -> _SECPField {
+        SECP256K1_B.into()
+    }
+
+    fn generator() -> Coordinates<_SECPField> {
+        Coordinates {
+            x: SECP256K1_GX,
+            y: SECP256K1_GY,
+        }
+    }
+
+    fn order() -> BigUint {
+        SECP256K1_N.clone()
+    }
 
-impl FieldMod for Secp256K1Mod {
-    fn modulus() -> BigUint {
-        SECP256K1_PRIME.clone()
+    fn cofactor() -> u32 {
+        1
     }
 }
 
+/// A point on the secp256k1 curve.
+pub type Secp256K1Point = Point<Secp256K1>;
+
+/// Multiplies the generator by `scalar` in constant time using a precomputed table, which is
+/// built on first use. This is about six times faster than the ladder of
+/// [`Point::mul_secret`](crate::curve::Point::mul_secret) and about twice as fast as the
+/// variable-time `Secp256K1Point::generator() * scalar` (72 us, 447 us and 181 us in the
+/// `secp256k1_public_key`, `secp256k1_mul_secret` and `secp256k1_point_mul` benches).
+pub fn mul_generator(scalar: impl Into<BigUint>) -> Secp256K1Point {
+    SECP256K1_G_TABLE.mul(scalar)
+}
+
+/// The public key of a private key, computed in constant time with [`mul_generator`].
+///
+/// This is the entry point for deriving keys and for the nonce point of a signature.
+/// `Secp256K1Point::generator() * secret` and `*SECP256K1_G * secret` run in variable time and
+/// leak the secret through timing. Multiples of other points go through
+/// [`Point::mul_secret`](crate::curve::Point::mul_secret), or for an [`ECurvePoint`]
+/// [`ECurvePoint::mul_secret`](crate::ec::ECurvePoint::mul_secret) with [`SECP256K1_N`].
+pub fn public_key(secret: &BigUint) -> Secp256K1Point {
+    mul_generator(secret.clone())
+}
+
+/// Samples a scalar uniformly from `1..n`, suitable as a private key when `rng` is an
+/// [`OsRng`](crate::rng::OsRng).
+pub fn random_scalar(rng: &mut impl Rng) -> BigUint {
+    random_below(rng, &(&*SECP256K1_N - 1u32)) + 1u32
+}
+
+define_prime_field! {
+    /// The base field of the secp256k1 curve, of order [`SECP256K1_PRIME`].
+    pub Secp256K1Mod = SECP256K1_PRIME.clone()
+}
+
+define_prime_field! {
+    /// The scalar field of the secp256k1 curve, of order [`SECP256K1_N`]. Private keys and
+    /// other multipliers of points live here.
+    pub Secp256K1ScalarMod = SECP256K1_N.clone()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ec::PointType;
+    use crate::finite_field::FiniteField;
 
     #[test]
     fn test_gen() {
-        let g = SECP256K1_G.clone();
-        match g.p {
-            PointType::Point(c) => {
-                assert_eq!(c.x, SECP256K1_GX.clone());
-                assert_eq!(c.y, SECP256K1_GY.clone());
+        let c = SECP256K1_G
+            .coordinates()
+            .expect("Generator point not in the curve :/");
+        assert_eq!(c.x, SECP256K1_GX);
+        assert_eq!(c.y, SECP256K1_GY);
+    }
+
+    #[test]
+    fn test_reference_field() {
+        // The generator must also lie on the curve when using the generic BigUint field.
+        let curve: EllipticCurve<FiniteField<Secp256K1Mod>> =
+            EllipticCurve::new(SECP256K1_A, SECP256K1_B);
+        let g = curve.point_at(
+            FiniteField::from(SECP256K1_GX),
+            FiniteField::from(SECP256K1_GY),
+        );
+        assert!(g.is_valid());
+
+        let expected = &*SECP256K1_G * 12345u32;
+        let product = &g * 12345u32;
+        match (product.coordinates(), expected.coordinates()) {
+            (Some(a), Some(b)) => {
+                assert_eq!(a.x, FiniteField::from(b.x));
+                assert_eq!(a.y, FiniteField::from(b.y));
             }
-            _ => panic!("Generator point not in the curve :/"),
+            _ => panic!("Multiples of the generator should be points"),
+        }
+    }
+
+    #[test]
+    fn test_ecurve_mul_secret() {
+        let g = *SECP256K1_G;
+        let mut rng = crate::rng::SeededRng::new(23);
+        for k in [0u32.into(), 1u32.into(), random_scalar(&mut rng)] {
+            let expected = public_key(&k).to_sec(false);
+            assert_eq!(g.mul_secret(&k, &SECP256K1_N).to_sec(false), expected);
+        }
+        assert_eq!(
+            g.mul_secret(&SECP256K1_N, &SECP256K1_N),
+            SECP256K1.infinity()
+        );
+    }
+
+    #[test]
+    fn test_random_scalar() {
+        let mut rng = crate::rng::SeededRng::new(11);
+        for _ in 0..64 {
+            let k = random_scalar(&mut rng);
+            assert!(k > 0u32.into() && k < *SECP256K1_N);
+        }
+        assert_ne!(random_scalar(&mut rng), random_scalar(&mut rng));
+    }
+
+    #[test]
+    fn test_sec() {
+        let g = *SECP256K1_G;
+        let uncompressed = g.to_sec(false).unwrap();
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(
+            BigUint::from_bytes_be(&uncompressed),
+            BigUint::parse_bytes(
+                b"0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                  483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+                16
+            )
+            .unwrap()
+        );
+        let compressed = g.to_sec(true).unwrap();
+        assert_eq!(&compressed[1..], &uncompressed[1..33]);
+        assert_eq!(compressed[0], 0x02);
+
+        for k in [1u32, 2, 3, 999, 123456] {
+            let p = g * k;
+            for compressed in [false, true] {
+                assert_eq!(SECP256K1.parse_sec(&p.to_sec(compressed).unwrap()), Ok(p));
+            }
+        }
+    }
+
+    #[test]
+    fn test_point() {
+        let g = Secp256K1Point::generator();
+        assert_eq!(g.p, SECP256K1_G.p);
+        assert_eq!(Secp256K1Point::new(SECP256K1_GX, SECP256K1_GY), g);
+        for k in [2u32, 3, 12345] {
+            assert_eq!((g * k).p, (*SECP256K1_G * k).p);
+        }
+        assert_eq!(g * Secp256K1::order(), Secp256K1Point::infinity());
+        assert_eq!(Secp256K1Point::from_sec(&g.to_sec(true).unwrap()), Ok(g));
+    }
+
+    #[test]
+    fn test_mul_generator() {
+        let mut rng = crate::rng::SeededRng::new(12);
+        for _ in 0..8 {
+            let k = random_scalar(&mut rng);
+            assert_eq!(mul_generator(k.clone()), Secp256K1Point::generator() * k);
         }
+        assert_eq!(mul_generator(0u32), Secp256K1Point::infinity());
     }
 
     #[test]
     fn test_prime_field_order() {
-        let g = SECP256K1_G.clone();
+        let g = *SECP256K1_G;
         let res = g * SECP256K1_N.clone();
         assert_eq!(res, SECP256K1.infinity());
     }
@@ -0,0 +1,598 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Sub};
+
+use num_bigint::BigUint;
+
+use crate::algebra::{Field, Ring};
+use crate::ec::SecField;
+use crate::error::{Error, Result};
+use crate::finite_field::FiniteField;
+use crate::ladder::ConditionalSelect;
+use crate::secp256k1::Secp256K1Mod;
+
+/// The secp256k1 prime p = 2^256 - 2^32 - 977 as little-endian 64-bit limbs.
+const P: [u64; 4] = [
+    0xFFFFFFFEFFFFFC2F,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+/// 2^256 mod p = 2^32 + 977. Folding the high half of a product with this constant is what
+/// makes the reduction cheap.
+const R: u64 = 0x1000003D1;
+
+/// p - 2, the exponent used to invert through Fermat's little theorem.
+const P_MINUS_2: [u64; 4] = [
+    0xFFFFFFFEFFFFFC2D,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+/// (p + 1) / 4. Since p ≡ 3 mod 4, a^((p+1)/4) is a square root of every quadratic residue a.
+const SQRT_EXP: [u64; 4] = [
+    0xFFFFFFFFBFFFFF0C,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0x3FFFFFFFFFFFFFFF,
+];
+
+/// An element of the secp256k1 base field, stored as four little-endian 64-bit limbs and always
+/// fully reduced below p.
+///
+/// This is a drop-in replacement for `FiniteField<Secp256K1Mod>` that never allocates. The
+/// carry handling and final reductions select with masks instead of branching on the value.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Secp256K1Field([u64; 4]);
+
+impl Secp256K1Field {
+    pub const ZERO: Self = Self([0, 0, 0, 0]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    /// Parses a big-endian hex string of at most 64 digits at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string contains non hex digits, is too long, or the value is not below p.
+    pub const fn from_hex(hex: &str) -> Self {
+        let bytes = hex.as_bytes();
+        assert!(bytes.len() <= 64, "hex string longer than 64 digits");
+        let mut limbs = [0u64; 4];
+        let mut i = 0;
+        while i < bytes.len() {
+            let digit = match bytes[bytes.len() - 1 - i] {
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'f' => c - b'a' + 10,
+                c @ b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("invalid hex digit"),
+            };
+            limbs[i / 16] |= (digit as u64) << ((i % 16) * 4);
+            i += 1;
+        }
+        assert!(!geq_p(&limbs), "value not in field range");
+        Self(limbs)
+    }
+
+    /// Creates a field element from little-endian limbs, returning `None` if the value is not
+    /// below p.
+    pub const fn from_limbs(limbs: [u64; 4]) -> Option<Self> {
+        match geq_p(&limbs) {
+            true => None,
+            false => Some(Self(limbs)),
+        }
+    }
+
+    /// The little-endian limbs of this element.
+    pub const fn limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        (self.0[0] | self.0[1] | self.0[2] | self.0[3]) == 0
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_slice(&[
+            self.0[0] as u32,
+            (self.0[0] >> 32) as u32,
+            self.0[1] as u32,
+            (self.0[1] >> 32) as u32,
+            self.0[2] as u32,
+            (self.0[2] >> 32) as u32,
+            self.0[3] as u32,
+            (self.0[3] >> 32) as u32,
+        ])
+    }
+
+    /// Big-endian encoding in 32 bytes.
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.0.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a 32-byte big-endian encoding, failing if the length is wrong or the value is
+    /// not below p.
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength {
+                expected: 32,
+                actual: bytes.len(),
+            });
+        }
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().expect("chunks of 8 bytes"));
+        }
+        Self::from_limbs(limbs).ok_or_else(|| Error::OutOfRange {
+            value: BigUint::from_bytes_be(bytes),
+            modulus: crate::secp256k1::SECP256K1_PRIME.clone(),
+        })
+    }
+
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    /// Raises this element to a power given as little-endian limbs.
+    fn pow_limbs(&self, exponent: &[u64; 4]) -> Self {
+        let mut result = Self::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            if (exponent[i / 64] >> (i % 64)) & 1 == 1 {
+                result = &result * self;
+            }
+        }
+        result
+    }
+
+    pub fn exp(&self, exponent: impl Into<BigUint>) -> Self {
+        let exponent = exponent.into();
+        let mut result = Self::ONE;
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result = &result * self;
+            }
+        }
+        result
+    }
+
+    /// Returns the multiplicative inverse of this element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.pow_limbs(&P_MINUS_2)),
+        }
+    }
+
+    /// Computes `self / other`, returning `None` if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inv| self * &inv)
+    }
+
+    /// Returns a square root of this element, or `None` if it is not a quadratic residue.
+    /// The other root is its negation.
+    pub fn sqrt(&self) -> Option<Self> {
+        let root = self.pow_limbs(&SQRT_EXP);
+        match root.square() == *self {
+            true => Some(root),
+            false => None,
+        }
+    }
+}
+
+/// Returns whether the 256-bit value `a` is greater than or equal to p.
+const fn geq_p(a: &[u64; 4]) -> bool {
+    let mut i = 4;
+    while i > 0 {
+        i -= 1;
+        if a[i] != P[i] {
+            return a[i] > P[i];
+        }
+    }
+    true
+}
+
+/// Adds two 256-bit values, returning the sum and the carry out.
+fn add_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut carry = 0u64;
+    for ((r, a), b) in r.iter_mut().zip(a).zip(b) {
+        let (s, c1) = a.overflowing_add(*b);
+        let (s, c2) = s.overflowing_add(carry);
+        *r = s;
+        carry = (c1 | c2) as u64;
+    }
+    (r, carry)
+}
+
+/// Subtracts two 256-bit values, returning the difference and the borrow out.
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut borrow = 0u64;
+    for ((r, a), b) in r.iter_mut().zip(a).zip(b) {
+        let (d, b1) = a.overflowing_sub(*b);
+        let (d, b2) = d.overflowing_sub(borrow);
+        *r = d;
+        borrow = (b1 | b2) as u64;
+    }
+    (r, borrow)
+}
+
+/// Returns `a` if `mask` is all zeros and `b` if it is all ones.
+fn select(mask: u64, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut r = [0u64; 4];
+    for ((r, a), b) in r.iter_mut().zip(a).zip(b) {
+        *r = (b & mask) | (a & !mask);
+    }
+    r
+}
+
+/// Subtracts p from `a` if `a >= p` or if `carry` (a 257th bit) is set.
+fn reduce_once(a: [u64; 4], carry: u64) -> [u64; 4] {
+    let (s, borrow) = sub_limbs(&a, &P);
+    // Keep the difference when it did not underflow, or when the carry absorbed the borrow.
+    select(0u64.wrapping_sub(carry | (borrow ^ 1)), &a, &s)
+}
+
+/// Reduces a 512-bit product modulo p using 2^256 ≡ R (mod p).
+fn reduce_wide(t: [u64; 8]) -> [u64; 4] {
+    // lo + hi * R fits in 256 + 34 bits.
+    let mut r = [0u64; 4];
+    let mut carry: u128 = 0;
+    for (r, (lo, hi)) in r.iter_mut().zip(t[..4].iter().zip(&t[4..])) {
+        let v = *lo as u128 + (*hi as u128) * (R as u128) + carry;
+        *r = v as u64;
+        carry = v >> 64;
+    }
+
+    // Fold the remaining 34 bits. This can wrap past 2^256 at most once more, in which case
+    // r is tiny and adding R again cannot overflow.
+    let mut c = carry * R as u128;
+    for limb in r.iter_mut() {
+        let v = *limb as u128 + c;
+        *limb = v as u64;
+        c = v >> 64;
+    }
+    let mut c = c * R as u128;
+    for limb in r.iter_mut() {
+        let v = *limb as u128 + c;
+        *limb = v as u64;
+        c = v >> 64;
+    }
+
+    reduce_once(r, 0)
+}
+
+// T + T
+impl Add for Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn add(self, other: Self) -> Self::Output {
+        let (r, carry) = add_limbs(&self.0, &other.0);
+        Secp256K1Field(reduce_once(r, carry))
+    }
+}
+
+// &T + &T
+impl Add for &Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn add(self, other: Self) -> Self::Output {
+        *self + *other
+    }
+}
+
+// T - T
+impl Sub for Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn sub(self, other: Self) -> Self::Output {
+        let (r, borrow) = sub_limbs(&self.0, &other.0);
+        // On underflow r = a - b + 2^256, so subtracting R yields a - b + p.
+        let fix = select(0u64.wrapping_sub(borrow), &[0; 4], &[R, 0, 0, 0]);
+        let (r, _) = sub_limbs(&r, &fix);
+        Secp256K1Field(r)
+    }
+}
+
+// &T - &T
+impl Sub for &Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn sub(self, other: Self) -> Self::Output {
+        *self - *other
+    }
+}
+
+// T * T
+impl Mul for Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let mut t = [0u64; 8];
+        for (i, a) in self.0.iter().enumerate() {
+            let mut carry: u128 = 0;
+            for (j, b) in other.0.iter().enumerate() {
+                let v = t[i + j] as u128 + (*a as u128) * (*b as u128) + carry;
+                t[i + j] = v as u64;
+                carry = v >> 64;
+            }
+            t[i + 4] = carry as u64;
+        }
+        Secp256K1Field(reduce_wide(t))
+    }
+}
+
+// &T * &T
+impl Mul for &Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn mul(self, other: Self) -> Self::Output {
+        *self * *other
+    }
+}
+
+// T / T
+impl Div for Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.checked_div(&other).expect("attempt to divide by zero")
+    }
+}
+
+// &T / &T
+impl Div for &Secp256K1Field {
+    type Output = Secp256K1Field;
+
+    fn div(self, other: Self) -> Self::Output {
+        *self / *other
+    }
+}
+
+impl Ring for Secp256K1Field {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl Field for Secp256K1Field {
+    fn inverse(&self) -> Option<Self> {
+        self.inverse()
+    }
+}
+
+impl SecField for Secp256K1Field {
+    fn byte_len() -> usize {
+        32
+    }
+
+    fn to_bytes_be(&self) -> Vec<u8> {
+        self.to_bytes_be().to_vec()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_be(bytes)
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        self.sqrt()
+    }
+
+    fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+}
+
+impl ConditionalSelect for Secp256K1Field {
+    fn conditional_select(a: &Self, b: &Self, choice: u64) -> Self {
+        Self(select(0u64.wrapping_sub(choice), &a.0, &b.0))
+    }
+}
+
+impl From<u32> for Secp256K1Field {
+    fn from(value: u32) -> Self {
+        Self([value as u64, 0, 0, 0])
+    }
+}
+
+impl From<&FiniteField<Secp256K1Mod>> for Secp256K1Field {
+    fn from(value: &FiniteField<Secp256K1Mod>) -> Self {
+        let mut limbs = [0u64; 4];
+        for (limb, digit) in limbs.iter_mut().zip(value.num().iter_u64_digits()) {
+            *limb = digit;
+        }
+        Self(limbs)
+    }
+}
+
+impl From<FiniteField<Secp256K1Mod>> for Secp256K1Field {
+    fn from(value: FiniteField<Secp256K1Mod>) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&Secp256K1Field> for FiniteField<Secp256K1Mod> {
+    fn from(value: &Secp256K1Field) -> Self {
+        FiniteField::from_reduced(value.to_biguint())
+    }
+}
+
+impl From<Secp256K1Field> for FiniteField<Secp256K1Mod> {
+    fn from(value: Secp256K1Field) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl Debug for Secp256K1Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Secp256K1Field(0x{:016x}{:016x}{:016x}{:016x})",
+            self.0[3], self.0[2], self.0[1], self.0[0]
+        )
+    }
+}
+
+impl Display for Secp256K1Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&FiniteField::<Secp256K1Mod>::from(self), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::SECP256K1_PRIME;
+
+    type Reference = FiniteField<Secp256K1Mod>;
+
+    /// Deterministic xorshift values so the cross-checks cover the whole 256-bit range.
+    fn samples() -> Vec<Secp256K1Field> {
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let p = Reference::from_unreduced(SECP256K1_PRIME.clone() - 1u32);
+        let mut samples: Vec<Secp256K1Field> = vec![
+            0u32.into(),
+            1u32.into(),
+            2u32.into(),
+            (&p).into(),
+            Secp256K1Field::from_hex(
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2E",
+            ),
+            Secp256K1Field::from_hex("100000000000000000000000000000000"),
+        ];
+        while samples.len() < 64 {
+            if let Some(fe) = Secp256K1Field::from_limbs([next(), next(), next(), next()]) {
+                samples.push(fe);
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let fe = Secp256K1Field::from_hex("0123456789abcdefFEDCBA9876543210");
+        assert_eq!(fe.limbs(), [0xFEDCBA9876543210, 0x0123456789abcdef, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "value not in field range")]
+    fn test_from_hex_out_of_range() {
+        let _ = Secp256K1Field::from_hex(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        );
+    }
+
+    #[test]
+    fn test_from_limbs() {
+        assert_eq!(Secp256K1Field::from_limbs(P), None);
+        assert_eq!(Secp256K1Field::from_limbs([7, 0, 0, 0]), Some(7u32.into()));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for a in samples() {
+            let reference = Reference::from(&a);
+            assert_eq!(Secp256K1Field::from(&reference), a);
+        }
+    }
+
+    #[test]
+    fn test_cross_check_add_sub() {
+        let samples = samples();
+        for a in &samples {
+            for b in &samples {
+                let (ra, rb) = (Reference::from(a), Reference::from(b));
+                assert_eq!(Reference::from(a + b), &ra + &rb);
+                assert_eq!(Reference::from(a - b), &ra - &rb);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cross_check_mul() {
+        let samples = samples();
+        for a in &samples {
+            for b in &samples {
+                let (ra, rb) = (Reference::from(a), Reference::from(b));
+                assert_eq!(Reference::from(a * b), &ra * &rb);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cross_check_div() {
+        let samples = samples();
+        for a in samples.iter().take(8) {
+            for b in samples.iter().skip(1).take(8) {
+                let (ra, rb) = (Reference::from(a), Reference::from(b));
+                assert_eq!(Reference::from(a / b), &ra / &rb);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        for a in samples().iter().skip(1).take(16) {
+            assert_eq!(a * &a.inverse().unwrap(), Secp256K1Field::ONE);
+        }
+        assert_eq!(Secp256K1Field::ZERO.inverse(), None);
+    }
+
+    #[test]
+    fn test_bytes() {
+        for a in samples() {
+            let bytes = a.to_bytes_be();
+            assert_eq!(bytes.to_vec(), Reference::from(&a).to_bytes_be());
+            assert_eq!(Secp256K1Field::from_bytes_be(&bytes), Ok(a));
+        }
+        assert!(Secp256K1Field::from_bytes_be(&[0xFF; 32]).is_err());
+        assert_eq!(
+            Secp256K1Field::from_bytes_be(&[0; 31]),
+            Err(Error::InvalidLength {
+                expected: 32,
+                actual: 31
+            })
+        );
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for a in samples() {
+            let square = a.square();
+            let root = square.sqrt().unwrap();
+            assert!(root == a || root == Secp256K1Field::ZERO - a);
+            assert_eq!(
+                square.sqrt(),
+                Reference::from(&square).sqrt().map(Into::into)
+            );
+        }
+        // -1 is not a square since p ≡ 3 mod 4.
+        assert_eq!((Secp256K1Field::ZERO - Secp256K1Field::ONE).sqrt(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let a: Secp256K1Field = 5u32.into();
+        assert_eq!(format!("{}", a), format!("{}", Reference::new(5u32)));
+    }
+}
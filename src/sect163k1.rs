@@ -0,0 +1,57 @@
+use crate::{
+    binary_curve::{BinaryCurvePoint, BinaryEllipticCurve},
+    binary_field::BinaryField,
+    define_binary_field,
+};
+use lazy_static::lazy_static;
+use num_bigint::BigUint;
+
+define_binary_field! {
+    /// The field GF(2^163) of the sect163k1 curve, reduced by x^163 + x^7 + x^6 + x^3 + 1.
+    pub Sect163Mod = [163, 7, 6, 3, 0]
+}
+
+/// An element of the base field of the sect163k1 curve.
+pub type Sect163Field = BinaryField<Sect163Mod, 3>;
+
+fn from_hex(hex: &[u8]) -> Sect163Field {
+    Sect163Field::new(BigUint::parse_bytes(hex, 16).unwrap())
+}
+
+lazy_static! {
+    /// The sect163k1 Koblitz curve y^2 + xy = x^3 + x^2 + 1 from SEC 2, also NIST K-163.
+    pub static ref SECT163K1: BinaryEllipticCurve<Sect163Field> =
+        BinaryEllipticCurve::new(Sect163Field::one(), Sect163Field::one());
+
+    /// The generator point on the sect163k1 curve.
+    pub static ref SECT163K1_G: BinaryCurvePoint<'static, Sect163Field> = SECT163K1.point_at(
+        from_hex(b"02FE13C0537BBC11ACAA07D793DE4E6D5E5C94EEE8"),
+        from_hex(b"0289070FB05D38FF58321F2E800536D538CCDAA3D9"),
+    );
+
+    /// The prime order of the generator on the sect163k1 curve. The curve has 2n points.
+    pub static ref SECT163K1_N: BigUint = BigUint::parse_bytes(
+        b"04000000000000000000020108A2E0CC0D99F8A5EF",
+        16
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ec::PointType;
+
+    #[test]
+    fn test_gen() {
+        assert_ne!(SECT163K1_G.p, PointType::Invalid);
+        assert_ne!(SECT163K1_G.p, PointType::Infinity);
+    }
+
+    #[test]
+    fn test_order() {
+        let g = *SECT163K1_G;
+        assert_eq!(g * SECT163K1_N.clone(), SECT163K1.infinity());
+        assert_ne!(g * (&*SECT163K1_N - 1u32), SECT163K1.infinity());
+    }
+}
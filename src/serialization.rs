@@ -0,0 +1,258 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! Field elements are written as big-endian hex strings padded to the byte length of the
+//! modulus, and points as hex strings of their compressed SEC 1 encoding. [`Coordinates`],
+//! [`PointType`] and [`EllipticCurve`] derive their implementations from those of the field.
+//!
+//! A [`Point`] knows its curve from its type and deserializes like any other value. An
+//! [`ECurvePoint`] borrows its curve, so deserializing one needs the curve at hand. Points on a
+//! [`KnownCurve`] deserialize directly as `ECurvePoint<'static, T>`; points on any other curve go
+//! through the [`DeserializeSeed`] implemented by `&EllipticCurve<T>`.
+//!
+//! [`Coordinates`]: crate::ec::Coordinates
+//! [`PointType`]: crate::ec::PointType
+use serde::de::{DeserializeSeed, Error as _};
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::curve::{CurveParams, Point};
+use crate::ec::{ECurvePoint, EllipticCurve, SecField};
+use crate::error::{self, Error};
+use crate::finite_field::{FieldMod, FiniteField};
+use crate::secp256k1::SECP256K1;
+use crate::secp256k1_field::Secp256K1Field;
+
+/// A field with one distinguished curve, against which deserialized points are resolved.
+pub trait KnownCurve: SecField + 'static {
+    fn curve() -> &'static EllipticCurve<Self>;
+}
+
+impl KnownCurve for Secp256K1Field {
+    fn curve() -> &'static EllipticCurve<Self> {
+        &SECP256K1
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> error::Result<Vec<u8>> {
+    let invalid = || Error::Parse(s.to_string());
+    // u8::from_str_radix alone would also accept a `+` sign in front of a digit.
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| invalid()))
+        .collect()
+}
+
+fn deserialize_hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    from_hex(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+impl<F: FieldMod> Serialize for FiniteField<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(&self.to_bytes_be()))
+    }
+}
+
+impl<'de, F: FieldMod> Deserialize<'de> for FiniteField<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_bytes_be(&deserialize_hex(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Secp256K1Field {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(&self.to_bytes_be()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp256K1Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_bytes_be(&deserialize_hex(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl<T: SecField> Serialize for ECurvePoint<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self
+            .to_sec(true)
+            .ok_or_else(|| S::Error::custom("cannot serialize an invalid point"))?;
+        serializer.serialize_str(&to_hex(&bytes))
+    }
+}
+
+/// Deserializes a point on this curve.
+impl<'de, 'a, T: SecField> DeserializeSeed<'de> for &'a EllipticCurve<T> {
+    type Value = ECurvePoint<'a, T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.parse_sec(&deserialize_hex(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl<'de, T: KnownCurve> Deserialize<'de> for ECurvePoint<'static, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::curve().deserialize(deserializer)
+    }
+}
+
+impl<C: CurveParams> Serialize for Point<C>
+where
+    C::Field: SecField,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self
+            .to_sec(true)
+            .ok_or_else(|| S::Error::custom("cannot serialize an invalid point"))?;
+        serializer.serialize_str(&to_hex(&bytes))
+    }
+}
+
+impl<'de, C: CurveParams> Deserialize<'de> for Point<C>
+where
+    C::Field: SecField,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_sec(&deserialize_hex(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+    use crate::ec::{Coordinates, PointType};
+    use crate::secp256k1::{Secp256K1Point, SECP256K1_G};
+
+    define_prime_field!(Field223Mod = 223u32);
+
+    type Field223 = FiniteField<Field223Mod>;
+
+    fn test_curve() -> EllipticCurve<Field223> {
+        EllipticCurve::new(0u32, 7u32)
+    }
+
+    #[test]
+    fn test_field_element() {
+        let a = Field223::new(200u32);
+        assert_eq!(serde_json::to_string(&a).unwrap(), "\"c8\"");
+        assert_eq!(serde_json::from_str::<Field223>("\"C8\"").unwrap(), a);
+
+        assert!(serde_json::from_str::<Field223>("\"df\"").is_err());
+        assert!(serde_json::from_str::<Field223>("\"00c8\"").is_err());
+        assert!(serde_json::from_str::<Field223>("\"c\"").is_err());
+        assert!(serde_json::from_str::<Field223>("\"zz\"").is_err());
+        assert!(serde_json::from_str::<Field223>("\"+f\"").is_err());
+        assert!(serde_json::from_str::<Field223>("200").is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_field() {
+        let gx = SECP256K1_G.to_sec(false).unwrap()[1..33].to_vec();
+        let x = Secp256K1Field::from_bytes_be(&gx).unwrap();
+        let json = serde_json::to_string(&x).unwrap();
+        assert_eq!(
+            json,
+            "\"79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\""
+        );
+        assert_eq!(serde_json::from_str::<Secp256K1Field>(&json).unwrap(), x);
+    }
+
+    #[test]
+    fn test_secp256k1_point() {
+        let json = serde_json::to_string(&*SECP256K1_G).unwrap();
+        assert_eq!(
+            json,
+            "\"0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\""
+        );
+        let g: ECurvePoint<'static, Secp256K1Field> = serde_json::from_str(&json).unwrap();
+        assert_eq!(g, *SECP256K1_G);
+
+        for k in 2u32..6 {
+            let p = *SECP256K1_G * k;
+            let json = serde_json::to_string(&p).unwrap();
+            assert_eq!(serde_json::from_str::<ECurvePoint<_>>(&json).unwrap(), p);
+        }
+
+        let infinity = serde_json::to_string(&SECP256K1.infinity()).unwrap();
+        assert_eq!(infinity, "\"00\"");
+        assert_eq!(
+            serde_json::from_str::<ECurvePoint<Secp256K1Field>>(&infinity).unwrap(),
+            SECP256K1.infinity()
+        );
+    }
+
+    #[test]
+    fn test_point() {
+        let g = Secp256K1Point::generator();
+        let json = serde_json::to_string(&g).unwrap();
+        assert_eq!(json, serde_json::to_string(&*SECP256K1_G).unwrap());
+        assert_eq!(serde_json::from_str::<Secp256K1Point>(&json).unwrap(), g);
+
+        let json = serde_json::to_string(&Secp256K1Point::infinity()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Secp256K1Point>(&json).unwrap(),
+            Secp256K1Point::infinity()
+        );
+        assert!(serde_json::from_str::<Secp256K1Point>("\"0501\"").is_err());
+
+        // "+2" would read as the byte 0x02 without the digit check.
+        let signed = serde_json::to_string(&g).unwrap().replacen("\"0", "\"+", 1);
+        assert!(signed.starts_with("\"+2"));
+        assert!(serde_json::from_str::<Secp256K1Point>(&signed).is_err());
+    }
+
+    #[test]
+    fn test_seed() {
+        let c = test_curve();
+        let p = c.point_at(47u32, 71u32);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, "\"032f\"");
+
+        let mut de = serde_json::Deserializer::from_str(&json);
+        assert_eq!((&c).deserialize(&mut de).unwrap(), p);
+
+        // The uncompressed form is accepted too.
+        let mut de = serde_json::Deserializer::from_str("\"042f47\"");
+        assert_eq!((&c).deserialize(&mut de).unwrap(), p);
+
+        // 4^3 + 7 = 71 is not a square mod 223.
+        let mut de = serde_json::Deserializer::from_str("\"0204\"");
+        assert!((&c).deserialize(&mut de).is_err());
+    }
+
+    #[test]
+    fn test_invalid_point() {
+        let c = test_curve();
+        let invalid = c.point_at(200u32, 119u32);
+        assert!(serde_json::to_string(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_derived() {
+        let c = test_curve();
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(json, r#"{"a":"00","b":"07"}"#);
+        assert_eq!(
+            serde_json::from_str::<EllipticCurve<Field223>>(&json).unwrap(),
+            c
+        );
+
+        let p: PointType<Field223> = PointType::Point(Coordinates {
+            x: 47u32.into(),
+            y: 71u32.into(),
+        });
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, r#"{"Point":{"x":"2f","y":"47"}}"#);
+        assert_eq!(
+            serde_json::from_str::<PointType<Field223>>(&json).unwrap(),
+            p
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Shamir secret sharing over a prime field, with Feldman commitments for secp256k1 keys.
+//!
+//! A secret s is hidden as the constant term of a random polynomial f of degree t - 1, and the
+//! share with index i is f(i). Any t shares determine f, and so s, by interpolation, while
+//! fewer reveal nothing about s. [`split_verifiable`] also publishes the points a_j * G for the
+//! coefficients a_j of f, so every custodian can check its share against them.
+use std::collections::HashSet;
+
+use num_bigint::BigUint;
+
+use crate::error::{Error, Result};
+use crate::finite_field::{FieldMod, FiniteField};
+use crate::polynomial::Polynomial;
+use crate::rng::Rng;
+use crate::secp256k1::{public_key, Secp256K1Point, Secp256K1ScalarMod};
+
+/// A commitment a_j * G to one coefficient of the sharing polynomial.
+pub type Commitment = Secp256K1Point;
+
+/// One share of a secret: the sharing polynomial evaluated at a non-zero index.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Share<F: FieldMod> {
+    index: u32,
+    value: FiniteField<F>,
+}
+
+impl<F: FieldMod> Share<F> {
+    /// Creates a share, failing if `index` is zero or not below the field modulus.
+    pub fn new(index: u32, value: FiniteField<F>) -> Result<Self> {
+        if index == 0 || BigUint::from(index) >= *F::modulus() {
+            return Err(Error::InvalidShareIndex(index));
+        }
+        Ok(Self { index, value })
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn value(&self) -> &FiniteField<F> {
+        &self.value
+    }
+
+    /// Encodes the share as the big-endian 4-byte index followed by the big-endian value,
+    /// padded to the byte length of the modulus.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.index.to_be_bytes().to_vec();
+        bytes.extend(self.value.to_bytes_be());
+        bytes
+    }
+
+    /// Decodes a share produced by [`Share::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let expected = 4 + F::constants().byte_len;
+        if bytes.len() != expected {
+            return Err(Error::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        let index = u32::from_be_bytes(bytes[..4].try_into().expect("length checked"));
+        Self::new(index, FiniteField::from_bytes_be(&bytes[4..])?)
+    }
+
+    fn x(&self) -> FiniteField<F> {
+        FiniteField::from_reduced(self.index.into())
+    }
+}
+
+/// Draws the coefficients of a sharing polynomial of degree `threshold - 1` with the secret as
+/// constant term, after checking that `shares` distinct indices exist.
+fn sharing_coefficients<F: FieldMod>(
+    secret: &FiniteField<F>,
+    threshold: usize,
+    shares: usize,
+    rng: &mut impl Rng,
+) -> Result<Vec<FiniteField<F>>> {
+    let too_many = u32::try_from(shares).map_or(true, |n| BigUint::from(n) >= *F::modulus());
+    if threshold == 0 || threshold > shares || too_many {
+        return Err(Error::InvalidThreshold { threshold, shares });
+    }
+    let mut coeffs = vec![secret.clone()];
+    coeffs.extend((1..threshold).map(|_| FiniteField::random(rng)));
+    Ok(coeffs)
+}
+
+fn evaluate_shares<F: FieldMod>(coeffs: &[FiniteField<F>], shares: usize) -> Vec<Share<F>> {
+    let f = Polynomial::new(coeffs.to_vec());
+    (1..=shares as u32)
+        .map(|index| Share {
+            index,
+            value: f.evaluate(&FiniteField::from_reduced(index.into())),
+        })
+        .collect()
+}
+
+/// Splits `secret` into `shares` shares with indices 1 to `shares`, any `threshold` of which
+/// recover it.
+///
+/// Fails unless 1 <= `threshold` <= `shares` < p. The polynomial coefficients are drawn from
+/// `rng`, which must be an [`OsRng`](crate::rng::OsRng) for real secrets.
+pub fn split<F: FieldMod>(
+    secret: &FiniteField<F>,
+    threshold: usize,
+    shares: usize,
+    rng: &mut impl Rng,
+) -> Result<Vec<Share<F>>> {
+    let coeffs = sharing_coefficients(secret, threshold, shares, rng)?;
+    Ok(evaluate_shares(&coeffs, shares))
+}
+
+/// Recovers the secret from shares by Lagrange interpolation at zero.
+///
+/// Every share is used, so given at least the threshold of consistent shares the result is the
+/// secret. With fewer shares, or a corrupted one, the result is an unrelated value; use
+/// [`combine_verified`] to detect that.
+pub fn combine<F: FieldMod>(shares: &[Share<F>]) -> Result<FiniteField<F>> {
+    if shares.is_empty() {
+        return Err(Error::NotEnoughShares {
+            required: 1,
+            actual: 0,
+        });
+    }
+    let mut seen = HashSet::new();
+    for share in shares {
+        if !seen.insert(share.index) {
+            return Err(Error::DuplicateShare(share.index));
+        }
+    }
+    let points: Vec<_> = shares.iter().map(|s| (s.x(), s.value.clone())).collect();
+    let f = Polynomial::interpolate(&points).expect("share indices are distinct");
+    Ok(f.evaluate(&FiniteField::zero()))
+}
+
+/// Splits a secp256k1 scalar like [`split`] and returns the Feldman commitments a_j * G to
+/// the coefficients of the sharing polynomial, constant term first. The first commitment is
+/// the public key of the secret.
+pub fn split_verifiable(
+    secret: &FiniteField<Secp256K1ScalarMod>,
+    threshold: usize,
+    shares: usize,
+    rng: &mut impl Rng,
+) -> Result<(Vec<Share<Secp256K1ScalarMod>>, Vec<Commitment>)> {
+    let coeffs = sharing_coefficients(secret, threshold, shares, rng)?;
+    let commitments = coeffs.iter().map(|a| public_key(a.num())).collect();
+    Ok((evaluate_shares(&coeffs, shares), commitments))
+}
+
+/// Checks a share against the dealer's commitments: f(i) * G must equal the sum of
+/// i^j * (a_j * G), which is evaluated with Horner's rule in the group.
+pub fn verify_share(share: &Share<Secp256K1ScalarMod>, commitments: &[Commitment]) -> bool {
+    let expected = commitments
+        .iter()
+        .rev()
+        .fold(Secp256K1Point::infinity(), |acc, c| acc * share.index + c);
+    public_key(share.value.num()) == expected
+}
+
+/// Recovers a secp256k1 scalar like [`combine`], after checking that there are at least as
+/// many shares as commitments and that every share matches them.
+pub fn combine_verified(
+    shares: &[Share<Secp256K1ScalarMod>],
+    commitments: &[Commitment],
+) -> Result<FiniteField<Secp256K1ScalarMod>> {
+    if shares.len() < commitments.len() {
+        return Err(Error::NotEnoughShares {
+            required: commitments.len(),
+            actual: shares.len(),
+        });
+    }
+    if let Some(bad) = shares.iter().find(|s| !verify_share(s, commitments)) {
+        return Err(Error::InconsistentShare(bad.index));
+    }
+    combine(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::define_prime_field;
+    use crate::rng::SeededRng;
+
+    define_prime_field!(Field7 = 7u32);
+    define_prime_field!(Field223 = 223u32);
+
+    type Scalar = FiniteField<Secp256K1ScalarMod>;
+
+    #[test]
+    fn test_split_combine() {
+        let mut rng = SeededRng::new(1);
+        let secret = FiniteField::<Field223>::new(42u32);
+        let shares = split(&secret, 3, 5, &mut rng).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        // Every subset of at least three shares recovers the secret.
+        for mask in 0u32..32 {
+            let subset: Vec<_> = shares
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, s)| s.clone())
+                .collect();
+            if subset.len() >= 3 {
+                assert_eq!(combine(&subset), Ok(secret.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_threshold_one() {
+        let mut rng = SeededRng::new(2);
+        let secret = FiniteField::<Field7>::new(5u32);
+        for share in split(&secret, 1, 6, &mut rng).unwrap() {
+            assert_eq!(share.value(), &secret);
+        }
+    }
+
+    #[test]
+    fn test_invalid_threshold() {
+        let mut rng = SeededRng::new(3);
+        let secret = FiniteField::<Field7>::new(5u32);
+        for (threshold, shares) in [(0, 3), (4, 3), (2, 7)] {
+            assert_eq!(
+                split(&secret, threshold, shares, &mut rng),
+                Err(Error::InvalidThreshold { threshold, shares })
+            );
+        }
+        assert!(split(&secret, 6, 6, &mut rng).is_ok());
+    }
+
+    #[test]
+    fn test_combine_errors() {
+        let mut rng = SeededRng::new(4);
+        let secret = FiniteField::<Field223>::new(42u32);
+        let shares = split(&secret, 2, 3, &mut rng).unwrap();
+        assert_eq!(
+            combine::<Field223>(&[]),
+            Err(Error::NotEnoughShares {
+                required: 1,
+                actual: 0
+            })
+        );
+        let duplicated = [shares[0].clone(), shares[1].clone(), shares[0].clone()];
+        assert_eq!(combine(&duplicated), Err(Error::DuplicateShare(1)));
+    }
+
+    #[test]
+    fn test_share_bytes() {
+        let share = Share::new(3, FiniteField::<Field223>::new(200u32)).unwrap();
+        let bytes = share.to_bytes();
+        assert_eq!(bytes, vec![0, 0, 0, 3, 200]);
+        assert_eq!(Share::from_bytes(&bytes), Ok(share));
+
+        assert_eq!(
+            Share::<Field223>::from_bytes(&[0, 0, 0, 3]),
+            Err(Error::InvalidLength {
+                expected: 5,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            Share::<Field223>::from_bytes(&[0, 0, 0, 0, 1]),
+            Err(Error::InvalidShareIndex(0))
+        );
+        assert_eq!(
+            Share::<Field223>::from_bytes(&[0, 0, 0, 223, 1]),
+            Err(Error::InvalidShareIndex(223))
+        );
+        assert!(Share::<Field223>::from_bytes(&[0, 0, 0, 1, 223]).is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_shares() {
+        let mut rng = SeededRng::new(5);
+        let secret = Scalar::random(&mut rng);
+        let shares = split(&secret, 2, 3, &mut rng).unwrap();
+        assert_eq!(combine(&shares[1..]), Ok(secret.clone()));
+
+        let bytes = shares[0].to_bytes();
+        assert_eq!(bytes.len(), 36);
+        assert_eq!(Share::from_bytes(&bytes).as_ref(), Ok(&shares[0]));
+    }
+
+    #[test]
+    fn test_feldman() {
+        let mut rng = SeededRng::new(6);
+        let secret = Scalar::random(&mut rng);
+        let (shares, commitments) = split_verifiable(&secret, 2, 3, &mut rng).unwrap();
+        assert_eq!(commitments.len(), 2);
+        assert_eq!(
+            commitments[0],
+            Secp256K1Point::generator() * secret.num().clone()
+        );
+        for share in &shares {
+            assert!(verify_share(share, &commitments));
+        }
+        assert_eq!(combine_verified(&shares[..2], &commitments), Ok(secret));
+
+        let mut tampered = shares.clone();
+        tampered[1].value = &tampered[1].value + &Scalar::one();
+        assert!(!verify_share(&tampered[1], &commitments));
+        assert_eq!(
+            combine_verified(&tampered, &commitments),
+            Err(Error::InconsistentShare(2))
+        );
+        assert_eq!(
+            combine_verified(&shares[..1], &commitments),
+            Err(Error::NotEnoughShares {
+                required: 2,
+                actual: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Prime fields with a modulus below 2^64, using native integer arithmetic.
+//!
+//! [`SmallField`] carries its modulus as a const generic, so elements are a single `u64` and
+//! never allocate. It is meant for the toy curves of the book, where exhaustive tests over
+//! every point are cheap enough to run on each build.
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::algebra::{Field, Ring};
+use crate::number_theory::{is_prime_u64, mul_mod_u64, pow_mod_u64};
+
+/// An element of the prime field F_P, always fully reduced below `P`.
+///
+/// `P` is checked for primality at compile time: naming `SmallField<221>` compiles, but
+/// constructing an element of it does not.
+///
+/// ```compile_fail
+/// use programming_bitcoin::small_field::SmallField;
+///
+/// let _ = SmallField::<221>::one();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmallField<const P: u64>(u64);
+
+impl<const P: u64> SmallField<P> {
+    const PRIME: () = assert!(P > 2 && is_prime_u64(P), "modulus is not an odd prime");
+
+    /// Creates a field element, reducing `value` modulo `P`.
+    pub const fn new(value: u64) -> Self {
+        let () = Self::PRIME;
+        Self(value % P)
+    }
+
+    pub const fn zero() -> Self {
+        Self::new(0)
+    }
+
+    pub const fn one() -> Self {
+        Self::new(1)
+    }
+
+    /// The canonical representative of this element, in `0..P`.
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub const fn modulus() -> u64 {
+        P
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn square(&self) -> Self {
+        *self * *self
+    }
+
+    pub fn exp(&self, exponent: u64) -> Self {
+        Self(pow_mod_u64(self.0, exponent, P))
+    }
+
+    /// Returns the multiplicative inverse of this element, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.exp(P - 2)),
+        }
+    }
+
+    /// Computes `self / other`, returning `None` if `other` is zero.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inv| *self * inv)
+    }
+}
+
+// T + T
+impl<const P: u64> Add for SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn add(self, other: Self) -> Self::Output {
+        // The sum can exceed 2^64 for moduli above 2^63.
+        let (sum, overflow) = self.0.overflowing_add(other.0);
+        match overflow || sum >= P {
+            true => SmallField(sum.wrapping_sub(P)),
+            false => SmallField(sum),
+        }
+    }
+}
+
+// &T + &T
+impl<const P: u64> Add for &SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn add(self, other: Self) -> Self::Output {
+        *self + *other
+    }
+}
+
+// T - T
+impl<const P: u64> Sub for SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        match self.0 >= other.0 {
+            true => SmallField(self.0 - other.0),
+            false => SmallField(self.0.wrapping_sub(other.0).wrapping_add(P)),
+        }
+    }
+}
+
+// &T - &T
+impl<const P: u64> Sub for &SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        *self - *other
+    }
+}
+
+// T * T
+impl<const P: u64> Mul for SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        SmallField(mul_mod_u64(self.0, other.0, P))
+    }
+}
+
+// &T * &T
+impl<const P: u64> Mul for &SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        *self * *other
+    }
+}
+
+// T / T
+impl<const P: u64> Div for SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.checked_div(&other).expect("attempt to divide by zero")
+    }
+}
+
+// &T / &T
+impl<const P: u64> Div for &SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn div(self, other: Self) -> Self::Output {
+        *self / *other
+    }
+}
+
+impl<const P: u64> Neg for SmallField<P> {
+    type Output = SmallField<P>;
+
+    fn neg(self) -> Self::Output {
+        SmallField::zero() - self
+    }
+}
+
+impl<const P: u64> Ring for SmallField<P> {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn one() -> Self {
+        Self::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl<const P: u64> Field for SmallField<P> {
+    fn inverse(&self) -> Option<Self> {
+        self.inverse()
+    }
+}
+
+impl<const P: u64> From<u32> for SmallField<P> {
+    fn from(value: u32) -> Self {
+        Self::new(value as u64)
+    }
+}
+
+impl<const P: u64> From<u64> for SmallField<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const P: u64> Display for SmallField<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FieldElement<{}>({})", P, self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+    use crate::define_prime_field;
+    use crate::finite_field::FiniteField;
+
+    type F223 = SmallField<223>;
+
+    define_prime_field!(Field223Mod = 223u32);
+
+    #[test]
+    fn test_matches_finite_field() {
+        type Reference = FiniteField<Field223Mod>;
+        let check = |fe: F223, reference: Reference| {
+            assert_eq!(BigUint::from(fe.value()), *reference.num());
+        };
+        for a in 0u32..223 {
+            for b in 0u32..223 {
+                let (x, y) = (F223::from(a), F223::from(b));
+                let (rx, ry) = (Reference::from(a), Reference::from(b));
+                check(x + y, &rx + &ry);
+                check(x - y, &rx - &ry);
+                check(x * y, &rx * &ry);
+                if b != 0 {
+                    check(x / y, &rx / &ry);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduces_input() {
+        assert_eq!(F223::from(223u32), F223::zero());
+        assert_eq!(F223::from(1000u32).value(), 1000 % 223);
+        assert_eq!(-F223::from(1u32), F223::from(222u32));
+        assert_eq!(-F223::zero(), F223::zero());
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(F223::zero().inverse(), None);
+        for a in 1u32..223 {
+            let a = F223::from(a);
+            assert_eq!(a * a.inverse().unwrap(), F223::one());
+        }
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        // The largest 64-bit prime, where sums and differences wrap around u64.
+        type F = SmallField<18446744073709551557>;
+        let minus_one = F::new(u64::MAX - 59);
+        assert_eq!(minus_one + minus_one, -F::new(2));
+        assert_eq!(F::one() - minus_one, F::new(2));
+        assert_eq!(minus_one * minus_one, F::one());
+        assert_eq!(minus_one.inverse(), Some(minus_one));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(F223::from(5u32).to_string(), "FieldElement<223>(5)");
+    }
+}